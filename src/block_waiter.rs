@@ -0,0 +1,85 @@
+use crate::blockchain::BlockchainClient;
+use crate::config::ChainSettings;
+use crate::error::KeeperError;
+use crate::subscription_provider::SubscriptionProvider;
+use anyhow::Result;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::warn;
+
+// Waits for the chain to move past a block: follows newHeads when the client has a
+// subscription provider, polls every block time otherwise (or once the socket drops),
+// and gives up after `max_block_wait_seconds`
+#[derive(Debug, Clone)]
+pub struct BlockWaiter {
+    block_time: Duration,
+    max_wait: Duration,
+}
+
+impl BlockWaiter {
+    pub fn from_settings(settings: &ChainSettings) -> Self {
+        Self {
+            block_time: Duration::from_millis(settings.block_time_ms.max(1)),
+            max_wait: Duration::from_secs(settings.max_block_wait_seconds),
+        }
+    }
+
+    // First block number above `block`
+    pub async fn wait_for_block_after(&self, client: &BlockchainClient, block: u64) -> Result<u64> {
+        let wait = async {
+            if let Some(subscriptions) = client.subscription_provider() {
+                match Self::follow_new_heads(subscriptions, client, block).await {
+                    Ok(number) => return Ok(number),
+                    Err(e) => warn!("⚠️  newHeads unavailable, polling instead: {}", e),
+                }
+            }
+            self.poll(client, block).await
+        };
+        tokio::time::timeout(self.max_wait, wait)
+            .await
+            .map_err(|_| {
+                KeeperError::Rpc(format!(
+                    "No block after {} within {}s",
+                    block,
+                    self.max_wait.as_secs()
+                ))
+            })?
+    }
+
+    async fn poll(&self, client: &BlockchainClient, block: u64) -> Result<u64> {
+        loop {
+            tokio::time::sleep(self.block_time).await;
+            let current = client.get_block_number().await?;
+            if current > block {
+                return Ok(current);
+            }
+        }
+    }
+
+    async fn follow_new_heads(
+        subscriptions: &SubscriptionProvider,
+        client: &BlockchainClient,
+        block: u64,
+    ) -> Result<u64> {
+        let mut heads = subscriptions.subscribe_heads().await?;
+
+        // The block may have arrived before the subscription started
+        let latest = client.get_block_number().await?;
+        if latest > block {
+            return Ok(latest);
+        }
+        loop {
+            match heads.recv().await {
+                Ok(header) if header.number > block => return Ok(header.number),
+                Ok(_) | Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => {
+                    return Err(KeeperError::Rpc(format!(
+                        "newHeads subscription on {} dropped",
+                        subscriptions.label()
+                    ))
+                    .into());
+                }
+            }
+        }
+    }
+}
@@ -1,60 +1,255 @@
+use crate::config::GasPricing;
+use crate::contracts::gas_price_oracle::{unsigned_tx_bytes, GasPriceOracleContract};
+use crate::error::{classify_send_error, KeeperError};
+use crate::fees::FeeStrategy;
+use crate::journal::{JournalStatus, TxJournal};
 use crate::kms_signer::KmsSigner;
-use alloy::network::Ethereum;
-use alloy::primitives::Address;
+use crate::nonce_manager::NonceManager;
+use crate::private_relay::PrivateRelay;
+use crate::quorum::QuorumReader;
+use crate::rpc_pool::{EndpointHealth, RpcPool};
+use crate::subscription_provider::SubscriptionProvider;
+use alloy::eips::eip2718::Encodable2718;
+use alloy::network::{Ethereum, EthereumWallet, TransactionBuilder};
+use alloy::primitives::{Address, B256, U256};
 use alloy::providers::{Provider, ProviderBuilder};
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
 use std::str::FromStr;
-use std::sync::Arc;
-use url::Url;
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+// How many sent transactions the client remembers for the status endpoint
+const RECENT_TRANSACTIONS: usize = 20;
+
+// Resends of the same signed bytes when the node's answer to a broadcast was lost
+const RESEND_ATTEMPTS: u32 = 2;
+const RESEND_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+
+#[derive(Debug, Clone)]
+pub struct SentTransaction {
+    pub hash: B256,
+    pub nonce: u64,
+    pub sent_at: DateTime<Utc>,
+    // Gas limit * max fee, plus the L1 data fee on OP-stack chains
+    pub estimated_cost_wei: Option<U256>,
+}
+
+// Signed but not yet broadcast, part of a `presign` batch
+struct PresignedTransaction {
+    intent: String,
+    nonce: u64,
+    journal_id: Option<String>,
+    hash: B256,
+    raw_tx: Vec<u8>,
+    estimated_cost_wei: U256,
+}
+
+// Transactions signed together by `presign`, broadcast in nonce order with
+// `send_next_presigned`. Whatever the caller decides not to send goes back through
+// `discard_presigned`.
+pub struct PresignedBatch {
+    unsent: VecDeque<PresignedTransaction>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChainLiveness {
+    pub block_number: u64,
+    pub age_seconds: u64,
+    pub best_height: u64,
+    pub endpoint_heights: Vec<(String, Option<u64>)>,
+}
 
 #[derive(Clone)]
 pub struct BlockchainClient {
     provider: Arc<dyn Provider<Ethereum>>,
     keeper_address: Address,
+    nonce_manager: Arc<NonceManager>,
+    recent_transactions: Arc<Mutex<VecDeque<SentTransaction>>>,
+    // With a wallet, transactions are signed here and sent raw so the signed bytes can
+    // be journaled first; without one the provider signs (e.g. mocked providers)
+    wallet: Option<(EthereumWallet, u64)>,
+    journal: Option<Arc<TxJournal>>,
+    rpc_pool: Option<RpcPool>,
+    quorum: Option<Arc<QuorumReader>>,
+    // Prices every transaction sent without explicit fees
+    fee_strategy: Option<FeeStrategy>,
+    // OP-stack chains only
+    l1_fee_oracle: Option<GasPriceOracleContract>,
+    // WebSocket / IPC for newHeads; everything else goes over `provider`
+    subscriptions: Option<SubscriptionProvider>,
+    // Set on the clones handed to jobs that submit privately
+    private_relay: Option<Arc<PrivateRelay>>,
+    // Job using this clone, journaled with every send
+    job: Option<String>,
 }
 
 impl BlockchainClient {
+    #[allow(dead_code)] // Used in tests and public API
     pub async fn new(
         rpc_url: &str,
         expected_chain_id: u64,
         kms_key_id: &str,
         chain_config: &crate::config::ChainConfig,
     ) -> Result<Self> {
-        println!("🔗 Connecting to RPC: {}", rpc_url);
-
-        let url = Url::parse(rpc_url)?;
-
         let aws_region = chain_config.kms.as_ref()
             .and_then(|kms| kms.region.as_deref())
-            .ok_or_else(|| anyhow::anyhow!("KMS region not configured. Set AWS_REGION environment variable or configure region in config file"))?;
+            .ok_or_else(|| KeeperError::Config("KMS region not configured. Set AWS_REGION environment variable or configure region in config file".to_string()))?;
         let kms_signer = KmsSigner::new(
             kms_key_id.to_string(),
             aws_region.to_string(),
             expected_chain_id,
         )
         .await?;
+
+        let pool = RpcPool::single(expected_chain_id, rpc_url)?;
+        Self::with_signer(&pool, expected_chain_id, &kms_signer).await
+    }
+
+    // Connects using an already initialized signer, so the KMS key is only fetched once
+    pub async fn with_signer(
+        pool: &RpcPool,
+        expected_chain_id: u64,
+        kms_signer: &KmsSigner,
+    ) -> Result<Self> {
+        let endpoints = pool.labels().join(", ");
+        info!("🔗 Connecting to RPC: {}", endpoints);
+
         let kms_address = kms_signer.address();
 
         let provider = ProviderBuilder::new()
             .wallet(kms_signer.as_alloy_signer().clone())
-            .connect_http(url);
+            .connect_client(pool.client());
 
-        let chain_id = provider.get_chain_id().await?;
+        let chain_id = provider
+            .get_chain_id()
+            .await
+            .map_err(|e| KeeperError::Rpc(format!("Failed to reach {}: {}", endpoints, e)))?;
         if chain_id != expected_chain_id {
-            return Err(anyhow::anyhow!(
+            return Err(KeeperError::Config(format!(
                 "Chain ID mismatch: expected {}, got {}",
-                expected_chain_id,
-                chain_id
-            ));
+                expected_chain_id, chain_id
+            ))
+            .into());
         }
 
-        println!("✅ Connected to chain {}", expected_chain_id);
-        println!("🔐 KMS Wallet address: {}", kms_address);
+        info!("✅ Connected to chain {}", expected_chain_id);
+        info!("🔐 KMS Wallet address: {}", kms_address);
 
-        Ok(Self {
-            provider: Arc::new(provider),
-            keeper_address: kms_address,
-        })
+        let mut client = Self::from_provider(Arc::new(provider), kms_address).with_wallet(
+            EthereumWallet::from(kms_signer.as_alloy_signer().clone()),
+            expected_chain_id,
+        );
+        client.rpc_pool = Some(pool.clone());
+        Ok(client)
+    }
+
+    // Wraps an existing provider, e.g. a mocked one in tests. The provider is expected
+    // to sign for `keeper_address` itself.
+    pub fn from_provider(provider: Arc<dyn Provider<Ethereum>>, keeper_address: Address) -> Self {
+        Self {
+            nonce_manager: Arc::new(NonceManager::new(provider.clone(), keeper_address)),
+            provider,
+            keeper_address,
+            recent_transactions: Arc::new(Mutex::new(VecDeque::new())),
+            wallet: None,
+            journal: None,
+            rpc_pool: None,
+            quorum: None,
+            fee_strategy: None,
+            l1_fee_oracle: None,
+            subscriptions: None,
+            private_relay: None,
+            job: None,
+        }
+    }
+
+    pub fn with_wallet(mut self, wallet: EthereumWallet, chain_id: u64) -> Self {
+        self.wallet = Some((wallet, chain_id));
+        self
+    }
+
+    pub fn with_journal(mut self, journal: Arc<TxJournal>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    // Endpoint health when connected through an RPC pool
+    pub fn rpc_health(&self) -> Vec<EndpointHealth> {
+        self.rpc_pool
+            .as_ref()
+            .map(|pool| pool.health())
+            .unwrap_or_default()
+    }
+
+    pub fn with_quorum(mut self, quorum: Arc<QuorumReader>) -> Self {
+        self.quorum = Some(quorum);
+        self
+    }
+
+    pub fn with_fee_strategy(mut self, fee_strategy: FeeStrategy) -> Self {
+        self.fee_strategy = Some(fee_strategy);
+        self
+    }
+
+    // Prices the L1 data fee of OP-stack transactions through the GasPriceOracle predeploy
+    pub fn with_l1_fee_oracle(mut self) -> Self {
+        self.l1_fee_oracle = Some(GasPriceOracleContract::new(self.provider.clone()));
+        self
+    }
+
+    // None on chains without an L1 data fee
+    pub async fn estimate_l1_fee(
+        &self,
+        tx: &alloy::rpc::types::TransactionRequest,
+    ) -> Result<Option<U256>> {
+        let Some(oracle) = &self.l1_fee_oracle else {
+            return Ok(None);
+        };
+        let chain_id = tx
+            .chain_id
+            .or(self.wallet.as_ref().map(|(_, chain_id)| *chain_id))
+            .unwrap_or_default();
+        let l1_fee = oracle
+            .get_l1_fee(unsigned_tx_bytes(tx, chain_id))
+            .await
+            .map_err(|e| KeeperError::Rpc(format!("GasPriceOracle.getL1Fee failed: {}", e)))?;
+        Ok(Some(l1_fee))
+    }
+
+    pub async fn l1_base_fee(&self) -> Result<Option<U256>> {
+        match &self.l1_fee_oracle {
+            Some(oracle) => Ok(Some(oracle.l1_base_fee().await?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn with_subscription_provider(mut self, subscriptions: SubscriptionProvider) -> Self {
+        self.subscriptions = Some(subscriptions);
+        self
+    }
+
+    pub fn subscription_provider(&self) -> Option<&SubscriptionProvider> {
+        self.subscriptions.as_ref()
+    }
+
+    pub fn with_private_relay(mut self, relay: Arc<PrivateRelay>) -> Self {
+        self.private_relay = Some(relay);
+        self
+    }
+
+    pub fn with_job(mut self, job: &str) -> Self {
+        self.job = Some(job.to_string());
+        self
+    }
+
+    pub fn quorum(&self) -> Option<&Arc<QuorumReader>> {
+        self.quorum.as_ref()
+    }
+
+    pub fn journal(&self) -> Option<&Arc<TxJournal>> {
+        self.journal.as_ref()
     }
 
     pub fn provider(&self) -> Arc<dyn Provider<Ethereum>> {
@@ -62,40 +257,595 @@ impl BlockchainClient {
     }
 
     pub async fn get_block_number(&self) -> Result<u64> {
-        let block_number = self.provider.get_block_number().await?;
+        let block_number = self
+            .provider
+            .get_block_number()
+            .await
+            .map_err(|e| KeeperError::Rpc(e.to_string()))?;
         Ok(block_number)
     }
 
-    pub async fn get_base_fee_per_gas(&self) -> Result<u128> {
+    // Age of the latest block and, with several endpoints, every endpoint's height. The
+    // pool's health check runs first so the latest block comes from an endpoint that
+    // isn't lagging.
+    pub async fn check_liveness(&self) -> Result<ChainLiveness> {
         use alloy::eips::BlockNumberOrTag;
+        let mut endpoint_heights = Vec::new();
+        if let Some(pool) = self.rpc_pool.as_ref().filter(|pool| pool.len() > 1) {
+            pool.check_health().await;
+            endpoint_heights = pool
+                .health()
+                .into_iter()
+                .map(|endpoint| (endpoint.endpoint, endpoint.block))
+                .collect();
+        }
+
         let block = self
             .provider
             .get_block_by_number(BlockNumberOrTag::Latest)
-            .await?
-            .ok_or_else(|| anyhow::anyhow!("Latest block not found"))?;
-        let base_fee = block
+            .await
+            .map_err(|e| KeeperError::Rpc(e.to_string()))?
+            .ok_or_else(|| KeeperError::Rpc("Latest block not found".to_string()))?;
+        let now = Utc::now().timestamp().max(0) as u64;
+        Ok(ChainLiveness {
+            block_number: block.header.number,
+            age_seconds: now.saturating_sub(block.header.timestamp),
+            best_height: endpoint_heights
+                .iter()
+                .filter_map(|(_, height)| *height)
+                .chain(std::iter::once(block.header.number))
+                .max()
+                .unwrap_or(block.header.number),
+            endpoint_heights,
+        })
+    }
+
+    // Base fee of the latest block, or eth_gasPrice on chains priced as legacy
+    pub async fn get_current_gas_price(&self) -> Result<u128> {
+        let pricing = self
+            .fee_strategy
+            .as_ref()
+            .map(|strategy| strategy.pricing())
+            .unwrap_or_default();
+        if pricing != GasPricing::Legacy {
+            match self.latest_base_fee().await? {
+                Some(base_fee) => return Ok(base_fee),
+                None if pricing == GasPricing::Eip1559 => {
+                    return Err(anyhow::anyhow!("No baseFeePerGas (pre-EIP-1559 chain)"));
+                }
+                None => {}
+            }
+        }
+        let gas_price = self
+            .provider
+            .get_gas_price()
+            .await
+            .map_err(|e| KeeperError::Rpc(format!("eth_gasPrice failed: {}", e)))?;
+        Ok(gas_price)
+    }
+
+    async fn latest_base_fee(&self) -> Result<Option<u128>> {
+        use alloy::eips::BlockNumberOrTag;
+        let block = self
+            .provider
+            .get_block_by_number(BlockNumberOrTag::Latest)
+            .await
+            .map_err(|e| KeeperError::Rpc(e.to_string()))?
+            .ok_or_else(|| KeeperError::Rpc("Latest block not found".to_string()))?;
+        Ok(block
             .header
             .base_fee_per_gas
-            .ok_or_else(|| anyhow::anyhow!("No baseFeePerGas (pre-EIP-1559 chain)"))?;
-        Ok(base_fee as u128)
+            .filter(|fee| *fee > 0)
+            .map(u128::from))
     }
 
     pub fn parse_address(addr: &str) -> Result<Address> {
         Address::from_str(addr).map_err(|e| anyhow::anyhow!("Invalid address {}: {}", addr, e))
     }
 
+    // Transactions without an explicit nonce get one from the shared allocator, so
+    // concurrent jobs on this client never reuse a nonce. With a journal, every step
+    // (intent, signed bytes, sent, rejected) is on disk before the next one starts.
+    #[tracing::instrument(
+        name = "send_transaction",
+        skip_all,
+        fields(intent = intent, nonce = tracing::field::Empty, tx_hash = tracing::field::Empty)
+    )]
     pub async fn send_transaction(
         &self,
-        tx: alloy::rpc::types::TransactionRequest,
+        intent: &str,
+        mut tx: alloy::rpc::types::TransactionRequest,
     ) -> Result<alloy::primitives::B256> {
-        println!("📤 Sending transaction...");
-        let pending = self.provider.send_transaction(tx).await?;
-        let tx_hash = *pending.tx_hash();
-        println!("✅ Transaction sent: {:?}", tx_hash);
+        let base_fee = self.apply_fee_strategy(&mut tx).await?;
+
+        let managed_nonce = match tx.nonce {
+            Some(_) => None,
+            None => {
+                let nonce = self.nonce_manager.next_nonce().await?;
+                tx.nonce = Some(nonce);
+                Some(nonce)
+            }
+        };
+
+        let nonce = tx.nonce.unwrap_or_default();
+        tracing::Span::current().record("nonce", nonce);
+        let journal_id = match self.journal_intent(intent, nonce) {
+            Ok(id) => id,
+            Err(e) => {
+                if let Some(nonce) = managed_nonce {
+                    self.release_nonce(nonce).await;
+                }
+                return Err(e);
+            }
+        };
+
+        info!("📤 Sending {} (nonce {})...", intent, nonce);
+        let (tx_hash, estimated_cost_wei) =
+            match self.submit(tx, journal_id.as_deref(), base_fee).await {
+                Ok(sent) => sent,
+                Err((e, maybe_broadcast)) => {
+                    // A signed tx that may have reached the node keeps its nonce and stays
+                    // unfinished in the journal for the next run to reconcile
+                    if !maybe_broadcast {
+                        if let Some(nonce) = managed_nonce {
+                            self.release_nonce(nonce).await;
+                        }
+                        self.journal_error(journal_id.as_deref(), &e);
+                    }
+                    return Err(e);
+                }
+            };
+        tracing::Span::current().record("tx_hash", tracing::field::display(tx_hash));
+        self.record_sent(journal_id.as_deref(), tx_hash, nonce, estimated_cost_wei);
         Ok(tx_hash)
     }
 
+    // Signs every transaction with increasing nonces before broadcasting any of them, then
+    // sends them back to back so they reach the node together. If one is refused the rest
+    // are not sent, since they could never be mined past the nonce gap. Without a local
+    // wallet they go out one at a time through `send_transaction`.
+    #[allow(dead_code)] // Used in tests and public API
+    pub async fn send_presigned(
+        &self,
+        txs: Vec<(&str, alloy::rpc::types::TransactionRequest)>,
+    ) -> Result<Vec<B256>> {
+        if self.wallet.is_none() {
+            let mut hashes = Vec::with_capacity(txs.len());
+            for (intent, tx) in txs {
+                hashes.push(self.send_transaction(intent, tx).await?);
+            }
+            return Ok(hashes);
+        }
+
+        let mut batch = self.presign(txs).await?;
+        let mut hashes = Vec::with_capacity(batch.unsent.len());
+        while !batch.unsent.is_empty() {
+            match self.send_next_presigned(&mut batch).await {
+                Ok(hash) => hashes.push(hash),
+                Err(e) => {
+                    if !hashes.is_empty() {
+                        warn!("⚠️  Presigned batch stopped after sending {:?}", hashes);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        Ok(hashes)
+    }
+
+    // Signs every transaction with increasing nonces without broadcasting any of them.
+    // The nonces are consecutive unless one fills a gap left by a released nonce.
+    pub async fn presign(
+        &self,
+        txs: Vec<(&str, alloy::rpc::types::TransactionRequest)>,
+    ) -> Result<PresignedBatch> {
+        if self.wallet.is_none() {
+            return Err(
+                KeeperError::Config("Presigning requires a local wallet".to_string()).into(),
+            );
+        }
+
+        let nonces = self.nonce_manager.next_nonces(txs.len()).await?;
+        let mut signed = VecDeque::with_capacity(txs.len());
+        for ((intent, mut tx), nonce) in txs.into_iter().zip(nonces.clone()) {
+            tx.nonce = Some(nonce);
+            let presigned = async {
+                let base_fee = self.apply_fee_strategy(&mut tx).await?;
+                let journal_id = self.journal_intent(intent, nonce)?;
+                match self.sign(tx, journal_id.as_deref(), base_fee).await {
+                    Ok((hash, raw_tx, estimated_cost_wei)) => Ok(PresignedTransaction {
+                        intent: intent.to_string(),
+                        nonce,
+                        journal_id,
+                        hash,
+                        raw_tx,
+                        estimated_cost_wei,
+                    }),
+                    Err((e, _)) => {
+                        self.journal_error(journal_id.as_deref(), &e);
+                        Err(e)
+                    }
+                }
+            }
+            .await;
+            match presigned {
+                Ok(presigned) => signed.push_back(presigned),
+                Err(e) => {
+                    // Nothing has been broadcast yet, so the whole range goes back
+                    for presigned in &signed {
+                        self.journal_error(presigned.journal_id.as_deref(), &e);
+                    }
+                    for nonce in nonces.iter().rev() {
+                        self.release_nonce(*nonce).await;
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        Ok(PresignedBatch { unsent: signed })
+    }
+
+    // Broadcasts the next transaction of the batch. If the node refuses it, it and the
+    // rest of the batch are given back, since they could never be mined past the gap; one
+    // that may have reached the node keeps its nonce.
+    pub async fn send_next_presigned(&self, batch: &mut PresignedBatch) -> Result<B256> {
+        let presigned = batch
+            .unsent
+            .pop_front()
+            .ok_or_else(|| anyhow::anyhow!("Presigned batch has nothing left to send"))?;
+        info!(
+            "📤 Sending {} (nonce {}, presigned)...",
+            presigned.intent, presigned.nonce
+        );
+        if let Err((e, maybe_broadcast)) = self.send_signed(presigned.hash, &presigned.raw_tx).await
+        {
+            if !maybe_broadcast {
+                batch.unsent.push_front(presigned);
+            }
+            let unsent = PresignedBatch {
+                unsent: std::mem::take(&mut batch.unsent),
+            };
+            self.discard_presigned(unsent, &e).await;
+            return Err(e);
+        }
+        self.record_sent(
+            presigned.journal_id.as_deref(),
+            presigned.hash,
+            presigned.nonce,
+            Some(presigned.estimated_cost_wei),
+        );
+        Ok(presigned.hash)
+    }
+
+    // Gives back the nonces of presigned transactions that won't be sent, highest first
+    pub async fn discard_presigned(&self, batch: PresignedBatch, reason: &anyhow::Error) {
+        for unsent in batch.unsent.iter().rev() {
+            self.journal_error(unsent.journal_id.as_deref(), reason);
+            self.release_nonce(unsent.nonce).await;
+        }
+    }
+
+    // Gives back a nonce whose transaction never reached the node. If later nonces are
+    // already out, the gap is filled right away with a 0-value self-transfer rather than
+    // left for a later allocation, which may never come if this run ends first.
+    async fn release_nonce(&self, nonce: u64) {
+        if self.nonce_manager.release(nonce).await
+            || !self.nonce_manager.claim_released(nonce).await
+        {
+            return;
+        }
+        warn!(
+            "⚠️  Nonce {} is free below later transactions, filling the gap",
+            nonce
+        );
+        match self.fill_nonce_gap(nonce).await {
+            Ok(tx_hash) => info!("🩹 Nonce gap {} filled by {:?}", nonce, tx_hash),
+            Err(e) => warn!(
+                "⚠️  Could not fill nonce gap {}, later transactions wait for it: {}",
+                nonce, e
+            ),
+        }
+    }
+
+    async fn fill_nonce_gap(&self, nonce: u64) -> Result<B256> {
+        let intent = format!("fill nonce gap {}", nonce);
+        let mut tx = alloy::rpc::types::TransactionRequest {
+            to: Some(self.keeper_address.into()),
+            value: Some(U256::ZERO),
+            nonce: Some(nonce),
+            gas: Some(21_000),
+            ..Default::default()
+        };
+        let prepared = async {
+            let base_fee = self.apply_fee_strategy(&mut tx).await?;
+            Ok::<_, anyhow::Error>((base_fee, self.journal_intent(&intent, nonce)?))
+        }
+        .await;
+        let (base_fee, journal_id) = match prepared {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                self.nonce_manager.release(nonce).await;
+                return Err(e);
+            }
+        };
+
+        match self.submit(tx, journal_id.as_deref(), base_fee).await {
+            Ok((tx_hash, estimated_cost_wei)) => {
+                self.record_sent(journal_id.as_deref(), tx_hash, nonce, estimated_cost_wei);
+                Ok(tx_hash)
+            }
+            Err((e, maybe_broadcast)) => {
+                if !maybe_broadcast {
+                    self.nonce_manager.release(nonce).await;
+                    self.journal_error(journal_id.as_deref(), &e);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    // Returns the quoted base fee (the gas price for legacy quotes), the floor any
+    // later fee adjustment must stay above
+    async fn apply_fee_strategy(
+        &self,
+        tx: &mut alloy::rpc::types::TransactionRequest,
+    ) -> Result<Option<u128>> {
+        if let Some(strategy) = &self.fee_strategy {
+            if tx.max_fee_per_gas.is_none() && tx.gas_price.is_none() {
+                let quote = strategy.estimate(self.provider.as_ref()).await?;
+                quote.apply(tx);
+                return Ok(Some(quote.base_fee_per_gas));
+            }
+        }
+        Ok(None)
+    }
+
+    fn journal_intent(&self, intent: &str, nonce: u64) -> Result<Option<String>> {
+        match &self.journal {
+            Some(journal) => journal
+                .record_intent(self.job.as_deref(), intent, nonce)
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn record_sent(
+        &self,
+        journal_id: Option<&str>,
+        tx_hash: B256,
+        nonce: u64,
+        estimated_cost_wei: Option<U256>,
+    ) {
+        info!("✅ Transaction sent: {:?}", tx_hash);
+        if let (Some(journal), Some(id)) = (&self.journal, journal_id) {
+            if let Err(e) = journal.record_sent(id, tx_hash) {
+                warn!(
+                    "⚠️  Failed to journal sent transaction {:?}: {}",
+                    tx_hash, e
+                );
+            }
+        }
+
+        if let Ok(mut recent) = self.recent_transactions.lock() {
+            if recent.len() == RECENT_TRANSACTIONS {
+                recent.pop_front();
+            }
+            recent.push_back(SentTransaction {
+                hash: tx_hash,
+                nonce,
+                sent_at: Utc::now(),
+                estimated_cost_wei,
+            });
+        }
+    }
+
+    // Signs and broadcasts, returning the hash and the estimated cost. Errors say whether
+    // the tx may have reached the node anyway.
+    async fn submit(
+        &self,
+        tx: alloy::rpc::types::TransactionRequest,
+        journal_id: Option<&str>,
+        base_fee: Option<u128>,
+    ) -> std::result::Result<(B256, Option<U256>), (anyhow::Error, bool)> {
+        if self.wallet.is_none() {
+            return match self.provider.send_transaction(tx).await {
+                Ok(pending) => Ok((*pending.tx_hash(), None)),
+                Err(e) => Err((classify_send_error(e).into(), false)),
+            };
+        }
+        let (tx_hash, raw_tx, estimated_cost) = self.sign(tx, journal_id, base_fee).await?;
+        self.send_signed(tx_hash, &raw_tx).await?;
+        Ok((tx_hash, Some(estimated_cost)))
+    }
+
+    // Broadcasts signed bytes. When the node's answer is lost, the same bytes are sent
+    // again, never a new signature: if the first send got through, the resend is only
+    // "already known". Still unsure after that, it fails with `SendUncertain`, which
+    // retries don't repeat; the nonce stays taken and the journal entry stays for the
+    // next reconcile.
+    async fn send_signed(
+        &self,
+        tx_hash: B256,
+        raw_tx: &[u8],
+    ) -> std::result::Result<(), (anyhow::Error, bool)> {
+        let mut last_error = match self.broadcast(tx_hash, raw_tx).await {
+            Ok(()) => return Ok(()),
+            Err((e, false)) => return Err((e, false)),
+            Err((e, true)) => e,
+        };
+        for attempt in 1..=RESEND_ATTEMPTS {
+            warn!(
+                "⚠️  Send of {:?} may have reached the node ({}), resending the same signed tx ({}/{})",
+                tx_hash, last_error, attempt, RESEND_ATTEMPTS
+            );
+            tokio::time::sleep(RESEND_DELAY).await;
+            match self.broadcast(tx_hash, raw_tx).await {
+                Ok(()) => return Ok(()),
+                Err((e, _)) if is_already_known(&e) => return Ok(()),
+                // A refusal now (e.g. nonce too low) may just mean the first one landed
+                Err((e, _)) => last_error = e,
+            }
+        }
+        let error = KeeperError::SendUncertain(format!("{:?}: {}", tx_hash, last_error));
+        Err((error.into(), true))
+    }
+
+    // Sends journaled bytes from an earlier run again, through this clone's private relay
+    // when it has one, so the relay's public fallback still applies to them
+    pub async fn rebroadcast(&self, tx_hash: B256, raw_tx: &[u8]) -> Result<()> {
+        self.broadcast(tx_hash, raw_tx).await.map_err(|(e, _)| e)
+    }
+
+    // Fills in gas and fees, signs with the local wallet and journals the signed bytes
+    async fn sign(
+        &self,
+        mut tx: alloy::rpc::types::TransactionRequest,
+        journal_id: Option<&str>,
+        base_fee: Option<u128>,
+    ) -> std::result::Result<(B256, Vec<u8>, U256), (anyhow::Error, bool)> {
+        let Some((wallet, chain_id)) = &self.wallet else {
+            let error = KeeperError::Config("Signing requires a local wallet".to_string());
+            return Err((error.into(), false));
+        };
+
+        tx.from = Some(self.keeper_address);
+        tx.chain_id = Some(*chain_id);
+        if tx.gas.is_none() {
+            let gas = self
+                .provider
+                .estimate_gas(tx.clone())
+                .await
+                .map_err(|e| (classify_send_error(e).into(), false))?;
+            tx.gas = Some(gas);
+        }
+        if tx.max_fee_per_gas.is_none() && tx.gas_price.is_none() {
+            let fees = self
+                .provider
+                .estimate_eip1559_fees()
+                .await
+                .map_err(|e| (classify_send_error(e).into(), false))?;
+            let tip = tx
+                .max_priority_fee_per_gas
+                .unwrap_or(fees.max_priority_fee_per_gas);
+            tx.max_priority_fee_per_gas = Some(tip);
+            tx.max_fee_per_gas = Some(fees.max_fee_per_gas.max(tip));
+        }
+        let estimated_cost = self
+            .fit_total_fee(&mut tx, base_fee)
+            .await
+            .map_err(|e| (e, false))?;
+
+        let envelope = tx.build(wallet).await.map_err(|e| {
+            let error = KeeperError::Kms(format!("Failed to sign transaction: {}", e));
+            (error.into(), false)
+        })?;
+        let raw_tx = envelope.encoded_2718();
+        let tx_hash = *envelope.tx_hash();
+        if let (Some(journal), Some(id)) = (&self.journal, journal_id) {
+            journal
+                .record_signed(id, tx_hash, &raw_tx)
+                .map_err(|e| (e, false))?;
+        }
+        Ok((tx_hash, raw_tx, estimated_cost))
+    }
+
+    async fn broadcast(
+        &self,
+        tx_hash: B256,
+        raw_tx: &[u8],
+    ) -> std::result::Result<(), (anyhow::Error, bool)> {
+        if let Some(relay) = &self.private_relay {
+            // Whatever the relay did with it, the public send below is the same tx
+            match relay.submit(self.provider.as_ref(), tx_hash, raw_tx).await {
+                Ok(()) => return Ok(()),
+                Err(e) => warn!(
+                    "⚠️  Private relay {} failed, sending publicly: {}",
+                    relay.label(),
+                    e
+                ),
+            }
+        }
+
+        match self.provider.send_raw_transaction(raw_tx).await {
+            Ok(_) => Ok(()),
+            // An error response means the node looked at the tx and refused it
+            Err(e) => {
+                let rejected = e.as_error_resp().is_some();
+                Err((classify_send_error(e).into(), !rejected))
+            }
+        }
+    }
+
+    // Prices the L1 data fee (OP-stack) and keeps gas * max fee (or gas price) + L1 fee
+    // under the per-transaction cap. Returns that estimated maximum cost.
+    async fn fit_total_fee(
+        &self,
+        tx: &mut alloy::rpc::types::TransactionRequest,
+        base_fee: Option<u128>,
+    ) -> Result<U256> {
+        let gas = tx.gas.unwrap_or_default();
+        let l1_fee = self.estimate_l1_fee(tx).await?.unwrap_or_default();
+        let price = tx.max_fee_per_gas.or(tx.gas_price);
+        if let (Some(strategy), Some(max_fee)) = (&self.fee_strategy, price) {
+            let fitted = strategy.fit_total_fee(max_fee, gas, l1_fee.saturating_to(), base_fee)?;
+            if fitted < max_fee {
+                warn!(
+                    "⚠️  Max fee lowered to {} gwei to fit the L1 data fee ({} wei) under the total fee cap",
+                    crate::fees::wei_to_gwei(fitted),
+                    l1_fee
+                );
+                if tx.max_fee_per_gas.is_some() {
+                    tx.max_fee_per_gas = Some(fitted);
+                    tx.max_priority_fee_per_gas =
+                        tx.max_priority_fee_per_gas.map(|tip| tip.min(fitted));
+                } else {
+                    tx.gas_price = Some(fitted);
+                }
+            }
+        }
+        let price = tx.max_fee_per_gas.or(tx.gas_price).unwrap_or_default();
+        let l2_cost = U256::from(gas) * U256::from(price);
+        if let Ok(Some(l1_base_fee)) = self.l1_base_fee().await {
+            info!(
+                "💸 Estimated max cost: {} wei L2 execution + {} wei L1 data (L1 base fee {} gwei)",
+                l2_cost,
+                l1_fee,
+                crate::fees::wei_to_gwei(l1_base_fee.saturating_to())
+            );
+        }
+        Ok(l2_cost + l1_fee)
+    }
+
+    fn journal_error(&self, journal_id: Option<&str>, error: &anyhow::Error) {
+        if let (Some(journal), Some(id)) = (&self.journal, journal_id) {
+            if let Err(e) = journal.record_error(id, JournalStatus::Dropped, &error.to_string()) {
+                warn!("⚠️  Failed to journal send error: {}", e);
+            }
+        }
+    }
+
+    // Most recent transactions sent by this client, newest last
+    pub fn recent_transactions(&self) -> Vec<SentTransaction> {
+        self.recent_transactions
+            .lock()
+            .map(|recent| recent.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
     pub fn keeper_address(&self) -> Address {
         self.keeper_address
     }
+
+    #[allow(dead_code)] // Used in tests and public API
+    pub fn nonce_manager(&self) -> &NonceManager {
+        &self.nonce_manager
+    }
+}
+
+// The node already has this exact transaction
+fn is_already_known(error: &anyhow::Error) -> bool {
+    let message = error.to_string().to_lowercase();
+    ["already known", "known transaction", "already imported"]
+        .iter()
+        .any(|known| message.contains(known))
 }
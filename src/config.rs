@@ -1,6 +1,7 @@
 use anyhow::Result;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use toml::map::Map;
@@ -15,12 +16,171 @@ pub struct ChainConfig {
     pub monitoring: MonitoringSettings,
     pub transaction: TransactionSettings,
     pub kms: Option<KmsSettings>,
+    pub boost_tokens: Option<Vec<BoostTokenLimit>>,
+    #[serde(default)]
+    pub boost: BoostSettings,
+    #[serde(default)]
+    pub distribute: DistributeSettings,
+    #[serde(default)]
+    pub lock: LockSettings,
+    #[serde(default)]
+    pub alerts: AlertSettings,
+    #[serde(default)]
+    pub report: ReportSettings,
+    #[serde(default)]
+    pub journal: JournalSettings,
+    #[serde(default)]
+    pub quorum: QuorumSettings,
+    // Per job ("claim-yield", "distribute-rewards")
+    #[serde(default)]
+    pub gas_ceiling: BTreeMap<String, GasCeilingSettings>,
+    pub profitability: Option<ProfitabilitySettings>,
+    pub private_relay: Option<PrivateRelaySettings>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ChainSettings {
     pub chain_id: u64,
+    // Primary endpoint; may be left empty when `rpc_endpoints` lists them all
+    #[serde(default)]
     pub rpc_url: String,
+    #[serde(default)]
+    pub rpc_endpoints: Vec<RpcEndpointSettings>,
+    // Endpoints more than this many blocks behind the best one are demoted
+    #[serde(default = "default_max_block_lag")]
+    pub max_block_lag: u64,
+    // First cooldown after a failure; doubles per consecutive failure
+    #[serde(default = "default_endpoint_cooldown_seconds")]
+    pub endpoint_cooldown_seconds: u64,
+    #[serde(default = "default_health_check_interval_seconds")]
+    pub health_check_interval_seconds: u64,
+    #[serde(default = "default_rpc_timeout_seconds")]
+    pub rpc_timeout_seconds: u64,
+    // Runs are skipped while the latest block is older than this; 0 disables the check
+    #[serde(default = "default_max_block_age_seconds")]
+    pub max_block_age_seconds: u64,
+    // OP-stack L2 (e.g. Soneium): transaction cost includes the L1 data fee
+    #[serde(default)]
+    pub op_stack: bool,
+    #[serde(default)]
+    pub gas_pricing: GasPricing,
+    // Polling interval while waiting for the next block
+    #[serde(default = "default_block_time_ms")]
+    pub block_time_ms: u64,
+    // Waiting for the next block fails after this long
+    #[serde(default = "default_max_block_wait_seconds")]
+    pub max_block_wait_seconds: u64,
+    // Receipts and new blocks are followed over newHeads on one of these instead of
+    // polled over HTTP; unset variables are ignored
+    pub ws_url: Option<String>,
+    pub ipc_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GasPricing {
+    // EIP-1559 when the chain reports a base fee, legacy gas price otherwise
+    #[default]
+    Auto,
+    Eip1559,
+    // `eth_gasPrice` * `gas_price_multiplier`, for chains and devnets without EIP-1559
+    Legacy,
+}
+
+// Lower priority is preferred; weights split traffic between endpoints of equal priority
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RpcEndpointSettings {
+    pub url: String,
+    #[serde(default)]
+    pub priority: u32,
+    #[serde(default = "default_endpoint_weight")]
+    pub weight: u32,
+}
+
+impl ChainSettings {
+    pub fn new(chain_id: u64, rpc_url: &str) -> Self {
+        Self {
+            chain_id,
+            rpc_url: rpc_url.to_string(),
+            rpc_endpoints: Vec::new(),
+            max_block_lag: default_max_block_lag(),
+            endpoint_cooldown_seconds: default_endpoint_cooldown_seconds(),
+            health_check_interval_seconds: default_health_check_interval_seconds(),
+            rpc_timeout_seconds: default_rpc_timeout_seconds(),
+            max_block_age_seconds: default_max_block_age_seconds(),
+            op_stack: false,
+            gas_pricing: GasPricing::default(),
+            block_time_ms: default_block_time_ms(),
+            max_block_wait_seconds: default_max_block_wait_seconds(),
+            ws_url: None,
+            ipc_path: None,
+        }
+    }
+
+    pub fn ws_url(&self) -> Option<&str> {
+        resolved(self.ws_url.as_deref())
+    }
+
+    pub fn ipc_path(&self) -> Option<&str> {
+        resolved(self.ipc_path.as_deref())
+    }
+
+    // `rpc_url` first (priority 0, weight 1), then `rpc_endpoints` in file order
+    pub fn endpoints(&self) -> Vec<RpcEndpointSettings> {
+        let mut endpoints = Vec::new();
+        if !self.rpc_url.trim().is_empty() {
+            endpoints.push(RpcEndpointSettings {
+                url: self.rpc_url.clone(),
+                priority: 0,
+                weight: default_endpoint_weight(),
+            });
+        }
+        for endpoint in &self.rpc_endpoints {
+            if !endpoints.iter().any(|e| e.url == endpoint.url) {
+                endpoints.push(endpoint.clone());
+            }
+        }
+        endpoints
+    }
+}
+
+fn default_max_block_lag() -> u64 {
+    5
+}
+
+fn default_endpoint_cooldown_seconds() -> u64 {
+    30
+}
+
+fn default_health_check_interval_seconds() -> u64 {
+    30
+}
+
+fn default_rpc_timeout_seconds() -> u64 {
+    30
+}
+
+// Optional settings filled from env vars that may be unset
+fn resolved(value: Option<&str>) -> Option<&str> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty() && !value.contains("${"))
+}
+
+fn default_block_time_ms() -> u64 {
+    2000
+}
+
+fn default_max_block_wait_seconds() -> u64 {
+    60
+}
+
+fn default_max_block_age_seconds() -> u64 {
+    300
+}
+
+fn default_endpoint_weight() -> u32 {
+    1
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -62,10 +222,51 @@ pub struct MonitoringSettings {
     pub timeout_gas_used: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TipStrategy {
+    // `max_priority_fee_gwei`, or the fee history tip when it isn't set
+    #[default]
+    Fixed,
+    // `fee_history_percentile` of the tips paid over the last `fee_history_blocks` blocks
+    FeeHistory,
+}
+
+// EIP-1559 fees for every transaction: max fee = base fee * multiplier + tip, capped.
+// On legacy chains: gas price = eth_gasPrice * multiplier, under the same cap.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TransactionSettings {
     pub value_wei: String,
     pub max_priority_fee_gwei: Option<f64>,
+    #[serde(default)]
+    pub tip_strategy: TipStrategy,
+    #[serde(default = "default_fee_history_percentile")]
+    pub fee_history_percentile: f64,
+    #[serde(default = "default_fee_history_blocks")]
+    pub fee_history_blocks: u64,
+    #[serde(default = "default_base_fee_multiplier")]
+    pub base_fee_multiplier: f64,
+    pub max_fee_per_gas_gwei: Option<f64>,
+    #[serde(default = "default_gas_price_multiplier")]
+    pub gas_price_multiplier: f64,
+    // Per transaction: gas limit * max fee, plus the L1 data fee on OP-stack chains
+    pub max_total_fee_eth: Option<f64>,
+}
+
+fn default_fee_history_percentile() -> f64 {
+    50.0
+}
+
+fn default_fee_history_blocks() -> u64 {
+    10
+}
+
+fn default_base_fee_multiplier() -> f64 {
+    2.0
+}
+
+fn default_gas_price_multiplier() -> f64 {
+    1.1
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -74,6 +275,271 @@ pub struct KmsSettings {
     pub region: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BoostSettings {
+    #[serde(default = "default_max_concurrent_campaigns")]
+    pub max_concurrent_campaigns: usize,
+    // Pay out campaigns without a [[boost_tokens]] allowlist
+    #[serde(default)]
+    pub allow_any_token: bool,
+}
+
+impl Default for BoostSettings {
+    fn default() -> Self {
+        Self {
+            max_concurrent_campaigns: default_max_concurrent_campaigns(),
+            allow_any_token: false,
+        }
+    }
+}
+
+fn default_max_concurrent_campaigns() -> usize {
+    4
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DistributeSubmission {
+    // snapshotVaultTVLs, wait for its receipt, then distribute
+    #[default]
+    Sequential,
+    // Both in one aggregate3 transaction on `executor_address`
+    Multicall,
+    // Both signed up front with increasing nonces and broadcast together
+    Presigned,
+}
+
+// How distribute-rewards sends snapshotVaultTVLs and distribute when a snapshot is due
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DistributeSettings {
+    #[serde(default)]
+    pub submission: DistributeSubmission,
+    // aggregate3 target, required for `multicall`. It becomes msg.sender for both calls,
+    // so it needs the keeper role on the RewardRedistributor and must only accept calls
+    // from the keeper; the public Multicall3 is refused.
+    pub executor_address: Option<String>,
+    // `presigned` only: distribute is signed before its snapshot exists, so it can't be
+    // estimated
+    #[serde(default = "default_distribute_gas_limit")]
+    pub distribute_gas_limit: u64,
+}
+
+impl Default for DistributeSettings {
+    fn default() -> Self {
+        Self {
+            submission: DistributeSubmission::default(),
+            executor_address: None,
+            distribute_gas_limit: default_distribute_gas_limit(),
+        }
+    }
+}
+
+fn default_distribute_gas_limit() -> u64 {
+    500_000
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LockBackendKind {
+    #[default]
+    File,
+    S3,
+}
+
+// Run lock shared by every keeper instance using the same signing key on a chain
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LockSettings {
+    #[serde(default)]
+    pub backend: LockBackendKind,
+    pub path: Option<String>,   // Directory for the file backend
+    pub s3_uri: Option<String>, // s3://bucket/prefix for the s3 backend
+    pub s3_region: Option<String>,
+    #[serde(default = "default_lock_ttl_seconds")]
+    pub ttl_seconds: u64,
+}
+
+impl Default for LockSettings {
+    fn default() -> Self {
+        Self {
+            backend: LockBackendKind::default(),
+            path: None,
+            s3_uri: None,
+            s3_region: None,
+            ttl_seconds: default_lock_ttl_seconds(),
+        }
+    }
+}
+
+impl LockSettings {
+    pub fn file_dir(&self) -> std::path::PathBuf {
+        self.path
+            .as_ref()
+            .map(std::path::PathBuf::from)
+            .unwrap_or_else(|| std::env::temp_dir().join("stablecoin-keeper-locks"))
+    }
+}
+
+fn default_lock_ttl_seconds() -> u64 {
+    900
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    #[default]
+    Generic,
+    Slack,
+    Discord,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WebhookSettings {
+    pub url: String,
+    #[serde(default)]
+    pub format: WebhookFormat,
+}
+
+// Alerting is off until at least one webhook is configured
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AlertSettings {
+    #[serde(default)]
+    pub webhooks: Vec<WebhookSettings>,
+    pub min_native_balance_eth: Option<f64>,
+    pub max_snapshot_age_seconds: Option<u64>,
+    // The same alert is sent at most once per window
+    #[serde(default = "default_alert_dedup_window_seconds")]
+    pub dedup_window_seconds: u64,
+    #[serde(default = "default_max_alerts_per_hour")]
+    pub max_alerts_per_hour: u32,
+}
+
+impl Default for AlertSettings {
+    fn default() -> Self {
+        Self {
+            webhooks: Vec::new(),
+            min_native_balance_eth: None,
+            max_snapshot_age_seconds: None,
+            dedup_window_seconds: default_alert_dedup_window_seconds(),
+            max_alerts_per_hour: default_max_alerts_per_hour(),
+        }
+    }
+}
+
+fn default_alert_dedup_window_seconds() -> u64 {
+    3600
+}
+
+fn default_max_alerts_per_hour() -> u32 {
+    20
+}
+
+// Where JSON run reports go; both destinations are optional
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ReportSettings {
+    pub dir: Option<String>,
+    pub s3_uri: Option<String>, // s3://bucket/prefix
+    pub s3_region: Option<String>,
+}
+
+// Transaction journal location. Must survive restarts (a volume, not the container fs)
+// for crash recovery to work.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct JournalSettings {
+    pub dir: Option<String>,
+}
+
+impl JournalSettings {
+    pub fn dir(&self) -> std::path::PathBuf {
+        self.dir
+            .as_ref()
+            .map(std::path::PathBuf::from)
+            .unwrap_or_else(|| std::env::temp_dir().join("stablecoin-keeper-journal"))
+    }
+}
+
+// Above `max_base_fee_gwei` the job defers to a later run, unless it has already been
+// deferring for `max_deferral_seconds`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GasCeilingSettings {
+    pub max_base_fee_gwei: f64,
+    pub max_deferral_seconds: Option<u64>,
+}
+
+// Dynamic yield threshold: claim/distribute only when pending yield covers the
+// transactions' estimated gas cost, converted to USDSC, times `min_multiple`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProfitabilitySettings {
+    #[serde(default = "default_min_multiple")]
+    pub min_multiple: f64,
+    // USDSC per native token; used without a price URL or when it fails
+    pub fixed_rate: Option<f64>,
+    // JSON price source for the native token, e.g. a CoinGecko simple/price URL
+    pub price_url: Option<String>,
+    // JSON pointer to the price in the response, e.g. "/ethereum/usd"
+    #[serde(default)]
+    pub price_pointer: String,
+    // Assumed when estimation reverts, e.g. distribute before its snapshot exists
+    #[serde(default = "default_fallback_gas")]
+    pub fallback_gas: u64,
+}
+
+fn default_min_multiple() -> f64 {
+    2.0
+}
+
+fn default_fallback_gas() -> u64 {
+    500_000
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelayMethod {
+    // eth_sendPrivateTransaction, valid until `max_blocks` blocks later
+    #[default]
+    PrivateTransaction,
+    // eth_sendBundle with the single transaction, resubmitted for every next block
+    Bundle,
+}
+
+// Flashbots-style private submission for selected jobs. Transactions not included
+// within `max_blocks` are broadcast to the public mempool.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PrivateRelaySettings {
+    pub url: String,
+    #[serde(default)]
+    pub method: RelayMethod,
+    // "claim-yield", "distribute-rewards", "boost-rewards"
+    pub jobs: Vec<String>,
+    #[serde(default = "default_relay_max_blocks")]
+    pub max_blocks: u64,
+    // Flashbots Protect style status API, queried as GET {status_url}/{tx hash}
+    pub status_url: Option<String>,
+    // Signs the X-Flashbots-Signature header; a random key per process when unset
+    pub auth_key: Option<String>,
+}
+
+fn default_relay_max_blocks() -> u64 {
+    25
+}
+
+// Quorum reads for safety-critical values; off unless `size` is at least 2
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct QuorumSettings {
+    #[serde(default)]
+    pub size: usize,
+    // Defaults to the chain's RPC endpoints, in order
+    #[serde(default)]
+    pub urls: Vec<String>,
+}
+
+// Allowlist entry for boost campaigns; amounts are in token units (not wei)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BoostTokenLimit {
+    pub address: String,
+    pub max_daily_amount: f64,
+    pub max_campaign_amount: f64,
+}
+
 impl ChainConfig {
     pub fn load(path: &str) -> Result<Self> {
         // Load .env file if it exists
@@ -149,7 +615,7 @@ impl ChainConfig {
         }
     }
 
-    fn substitute_env_vars(content: String) -> Result<String> {
+    pub fn substitute_env_vars(content: String) -> Result<String> {
         let re = Regex::new(r"\$\{([A-Z_][A-Z0-9_]*)\}")?;
         let mut result = content.clone();
 
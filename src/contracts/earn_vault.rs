@@ -1,6 +1,5 @@
-use alloy::network::Ethereum;
+use crate::blockchain::BlockchainClient;
 use alloy::primitives::{Address, TxKind, B256, U256};
-use alloy::providers::Provider;
 use alloy::rpc::types::{TransactionInput, TransactionRequest};
 use alloy::sol;
 use alloy::sol_types::SolCall;
@@ -17,15 +16,23 @@ sol! {
 #[derive(Clone)]
 pub struct EarnVaultContract {
     address: Address,
-    provider: Arc<dyn Provider<Ethereum>>,
+    client: Arc<BlockchainClient>,
 }
 
 impl EarnVaultContract {
-    pub fn new(address: Address, provider: Arc<dyn Provider<Ethereum>>) -> Self {
-        Self { address, provider }
+    pub fn new(address: Address, client: BlockchainClient) -> Self {
+        Self {
+            address,
+            client: Arc::new(client),
+        }
     }
 
-    pub async fn on_boost_reward(&self, token: Address, amount: U256) -> Result<B256> {
+    pub async fn on_boost_reward(
+        &self,
+        campaign: &str,
+        token: Address,
+        amount: U256,
+    ) -> Result<B256> {
         let call = IEarnVault::onBoostRewardCall { token, amount };
         let data: Vec<u8> = call.abi_encode();
 
@@ -35,9 +42,17 @@ impl EarnVaultContract {
             ..Default::default()
         };
 
-        // Use provider.send_transaction directly - provider already has signer attached
-        let pending = self.provider.send_transaction(tx).await?;
-        let tx_hash = *pending.tx_hash();
+        // Route through the client so the shared nonce allocator is used
+        let intent = Self::boost_reward_intent(campaign, token, amount);
+        let tx_hash = self.client.send_transaction(&intent, tx).await?;
         Ok(tx_hash)
     }
+
+    // Journal intent of `onBoostReward`, scoped to the campaign like the transfer before it
+    pub fn boost_reward_intent(campaign: &str, token: Address, amount: U256) -> String {
+        format!(
+            "onBoostReward {} of {:?} for campaign {}",
+            amount, token, campaign
+        )
+    }
 }
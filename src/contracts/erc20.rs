@@ -1,3 +1,6 @@
+use crate::blockchain::BlockchainClient;
+use crate::contracts::multicall3::BatchedReads;
+use crate::quorum::critical_call;
 use alloy::network::Ethereum;
 use alloy::primitives::{Address, Bytes, TxKind, B256, U256};
 use alloy::providers::Provider;
@@ -17,29 +20,50 @@ sol! {
     }
 }
 
+// Token metadata and one account's balance, read at one block
+#[derive(Debug, Clone)]
+pub struct TokenState {
+    pub decimals: u8,
+    pub symbol: String,
+    pub balance: U256,
+    pub block_number: u64,
+}
+
 #[derive(Clone)]
 pub struct ERC20Contract {
     address: Address,
     provider: Arc<dyn Provider<Ethereum>>,
+    client: Arc<BlockchainClient>,
 }
 
 impl ERC20Contract {
-    pub fn new(address: Address, provider: Arc<dyn Provider<Ethereum>>) -> Self {
-        Self { address, provider }
+    pub fn new(
+        address: Address,
+        provider: Arc<dyn Provider<Ethereum>>,
+        client: BlockchainClient,
+    ) -> Self {
+        Self {
+            address,
+            provider,
+            client: Arc::new(client),
+        }
     }
 
     pub async fn balance_of(&self, account: Address) -> Result<U256> {
         let call = IERC20::balanceOfCall { account };
         let data: Vec<u8> = call.abi_encode();
 
-        let result = self
-            .provider
-            .call(TransactionRequest {
+        let result = critical_call(
+            &self.client,
+            &self.provider,
+            "balanceOf",
+            TransactionRequest {
                 to: Some(TxKind::Call(self.address)),
                 input: TransactionInput::new(Bytes::from(data)),
                 ..Default::default()
-            })
-            .await?;
+            },
+        )
+        .await?;
 
         let decoded = IERC20::balanceOfCall::abi_decode_returns(&result)?;
         Ok(decoded)
@@ -83,7 +107,26 @@ impl ERC20Contract {
         Ok(decoded)
     }
 
-    pub async fn transfer(&self, to: Address, amount: U256) -> Result<B256> {
+    // Batched variant of `decimals`, `symbol` and `balance_of`
+    pub async fn token_state(&self, account: Address, block: Option<u64>) -> Result<TokenState> {
+        let mut reads = BatchedReads::new();
+        let decimals = reads.add(self.address, IERC20::decimalsCall {});
+        let symbol = reads.add(self.address, IERC20::symbolCall {});
+        let balance = reads.add(self.address, IERC20::balanceOfCall { account });
+
+        let results = reads
+            .execute(&self.client, &self.provider, "token state", block)
+            .await?;
+        Ok(TokenState {
+            decimals: results.get(&decimals)?,
+            symbol: results.get(&symbol)?,
+            balance: results.get(&balance)?,
+            block_number: results.block_number(),
+        })
+    }
+
+    // `campaign` scopes the journal intent, so equal payouts of two campaigns stay apart
+    pub async fn transfer(&self, campaign: &str, to: Address, amount: U256) -> Result<B256> {
         let call = IERC20::transferCall { to, amount };
         let data: Vec<u8> = call.abi_encode();
 
@@ -93,9 +136,27 @@ impl ERC20Contract {
             ..Default::default()
         };
 
-        // Use provider.send_transaction directly - provider already has signer attached
-        let pending = self.provider.send_transaction(tx).await?;
-        let tx_hash = *pending.tx_hash();
+        // Route through the client so the shared nonce allocator is used
+        let intent = Self::transfer_intent(campaign, self.address, to, amount);
+        let tx_hash = self.client.send_transaction(&intent, tx).await?;
         Ok(tx_hash)
     }
+
+    // Journal intent of `transfer`, specific enough to recognize the same payout later
+    pub fn transfer_intent(campaign: &str, token: Address, to: Address, amount: U256) -> String {
+        format!(
+            "transfer {} of {:?} to {:?} for campaign {}",
+            amount, token, to, campaign
+        )
+    }
+
+    // Token, amount and campaign (None in entries from before intents named it) of a
+    // journaled `transfer_intent`
+    pub fn parse_transfer_intent(intent: &str) -> Option<(Address, U256, Option<&str>)> {
+        let rest = intent.strip_prefix("transfer ")?;
+        let (amount, rest) = rest.split_once(" of ")?;
+        let (token, rest) = rest.split_once(" to ")?;
+        let campaign = rest.split_once(" for campaign ").map(|(_, id)| id);
+        Some((token.parse().ok()?, amount.parse().ok()?, campaign))
+    }
 }
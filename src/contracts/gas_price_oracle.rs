@@ -0,0 +1,85 @@
+use alloy::consensus::{SignableTransaction, TxEip1559, TxLegacy};
+use alloy::network::Ethereum;
+use alloy::primitives::{address, Address, Bytes, TxKind, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::{TransactionInput, TransactionRequest};
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use anyhow::Result;
+use std::sync::Arc;
+
+sol! {
+    #[sol(rpc)]
+    interface IGasPriceOracle {
+        function getL1Fee(bytes memory _data) external view returns (uint256);
+        function l1BaseFee() external view returns (uint256);
+    }
+}
+
+// OP-stack predeploy, the same address on every OP-stack chain
+pub const GAS_PRICE_ORACLE_ADDRESS: Address = address!("420000000000000000000000000000000000000F");
+
+#[derive(Clone)]
+pub struct GasPriceOracleContract {
+    provider: Arc<dyn Provider<Ethereum>>,
+}
+
+impl GasPriceOracleContract {
+    pub fn new(provider: Arc<dyn Provider<Ethereum>>) -> Self {
+        Self { provider }
+    }
+
+    // L1 data fee in wei for an unsigned RLP-encoded transaction
+    pub async fn get_l1_fee(&self, unsigned_tx: Bytes) -> Result<U256> {
+        let call = IGasPriceOracle::getL1FeeCall { _data: unsigned_tx };
+        let result = self.call(call.abi_encode()).await?;
+        Ok(IGasPriceOracle::getL1FeeCall::abi_decode_returns(&result)?)
+    }
+
+    pub async fn l1_base_fee(&self) -> Result<U256> {
+        let call = IGasPriceOracle::l1BaseFeeCall {};
+        let result = self.call(call.abi_encode()).await?;
+        Ok(IGasPriceOracle::l1BaseFeeCall::abi_decode_returns(&result)?)
+    }
+
+    async fn call(&self, data: Vec<u8>) -> Result<Bytes> {
+        Ok(self
+            .provider
+            .call(TransactionRequest {
+                to: Some(TxKind::Call(GAS_PRICE_ORACLE_ADDRESS)),
+                input: TransactionInput::new(Bytes::from(data)),
+                ..Default::default()
+            })
+            .await?)
+    }
+}
+
+// What getL1Fee expects: the transaction as it would be signed (legacy when it has a gas
+// price, EIP-1559 otherwise). Fields not set yet (e.g. gas before estimation) encode as
+// zero, a few bytes short at most.
+pub fn unsigned_tx_bytes(tx: &TransactionRequest, chain_id: u64) -> Bytes {
+    if let Some(gas_price) = tx.gas_price {
+        let unsigned = TxLegacy {
+            chain_id: Some(chain_id),
+            nonce: tx.nonce.unwrap_or_default(),
+            gas_price,
+            gas_limit: tx.gas.unwrap_or_default(),
+            to: tx.to.unwrap_or_default(),
+            value: tx.value.unwrap_or_default(),
+            input: tx.input.input().cloned().unwrap_or_default(),
+        };
+        return Bytes::from(unsigned.encoded_for_signing());
+    }
+    let unsigned = TxEip1559 {
+        chain_id,
+        nonce: tx.nonce.unwrap_or_default(),
+        gas_limit: tx.gas.unwrap_or_default(),
+        max_fee_per_gas: tx.max_fee_per_gas.unwrap_or_default(),
+        max_priority_fee_per_gas: tx.max_priority_fee_per_gas.unwrap_or_default(),
+        to: tx.to.unwrap_or_default(),
+        value: tx.value.unwrap_or_default(),
+        access_list: tx.access_list.clone().unwrap_or_default(),
+        input: tx.input.input().cloned().unwrap_or_default(),
+    };
+    Bytes::from(unsigned.encoded_for_signing())
+}
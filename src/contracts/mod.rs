@@ -1,4 +1,6 @@
 pub mod earn_vault;
 pub mod erc20;
+pub mod gas_price_oracle;
+pub mod multicall3;
 pub mod reward_redistributor;
 pub mod usdsc;
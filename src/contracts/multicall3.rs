@@ -0,0 +1,203 @@
+use crate::blockchain::BlockchainClient;
+use crate::error::KeeperError;
+use crate::quorum::critical_call_at;
+use alloy::network::Ethereum;
+use alloy::primitives::{address, Address, Bytes, TxKind, B256, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::{TransactionInput, TransactionRequest};
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use anyhow::Result;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+sol! {
+    #[sol(rpc)]
+    interface IMulticall3 {
+        struct Call3 {
+            address target;
+            bool allowFailure;
+            bytes callData;
+        }
+
+        struct Result {
+            bool success;
+            bytes returnData;
+        }
+
+        function aggregate3(Call3[] calldata calls) external payable returns (Result[] memory returnData);
+        function getBlockNumber() external view returns (uint256 blockNumber);
+        function getCurrentBlockTimestamp() external view returns (uint256 timestamp);
+    }
+}
+
+// Deployed at the same address on nearly every EVM chain
+pub const MULTICALL3_ADDRESS: Address = address!("cA11bde05977b3631167028862bE2a173976CA11");
+
+// The batched read found no contract at the Multicall3 address. The only batched-read
+// failure callers should answer by reading one by one; anything else (RPC errors, quorum
+// disagreement, reverts) is passed on.
+#[derive(Debug, thiserror::Error)]
+#[error("No Multicall3 at {address} for batched read {label}")]
+pub struct MulticallUnavailable {
+    pub address: Address,
+    pub label: String,
+}
+
+// Multicall3, or an executor contract with the same `aggregate3` entry point. The calls
+// run with the contract as `msg.sender`, so any role they need belongs to the contract.
+#[derive(Clone)]
+pub struct Multicall3Contract {
+    address: Address,
+    client: Arc<BlockchainClient>,
+}
+
+impl Multicall3Contract {
+    pub fn new(address: Address, client: BlockchainClient) -> Self {
+        Self {
+            address,
+            client: Arc::new(client),
+        }
+    }
+
+    // One transaction running every request's call in order; any revert reverts them all
+    pub fn aggregate3_request(
+        &self,
+        requests: &[TransactionRequest],
+    ) -> Result<TransactionRequest> {
+        let mut calls = Vec::with_capacity(requests.len());
+        for request in requests {
+            let Some(TxKind::Call(target)) = request.to else {
+                return Err(
+                    KeeperError::Config("aggregate3 calls need a target".to_string()).into(),
+                );
+            };
+            // aggregate3 doesn't forward value; aggregate3Value would be needed for that
+            if request.value.is_some_and(|value| value > U256::ZERO) {
+                return Err(KeeperError::Config(
+                    "aggregate3 calls can't carry a value".to_string(),
+                )
+                .into());
+            }
+            calls.push(IMulticall3::Call3 {
+                target,
+                allowFailure: false,
+                callData: request.input.input().cloned().unwrap_or_default(),
+            });
+        }
+
+        let call = IMulticall3::aggregate3Call { calls };
+        Ok(TransactionRequest {
+            to: Some(TxKind::Call(self.address)),
+            input: TransactionInput::new(Bytes::from(call.abi_encode())),
+            ..Default::default()
+        })
+    }
+
+    pub async fn aggregate3(&self, intent: &str, requests: &[TransactionRequest]) -> Result<B256> {
+        let tx = self.aggregate3_request(requests)?;
+        self.client.send_transaction(intent, tx).await
+    }
+}
+
+// Where a call's answer will be in the `BatchResults` of the batch it was added to
+pub struct BatchSlot<C> {
+    index: usize,
+    _call: PhantomData<C>,
+}
+
+// View calls read through a single aggregate3 eth_call: one round trip, and every
+// answer comes from the same block. Goes through the quorum when one is configured.
+pub struct BatchedReads {
+    multicall: Address,
+    calls: Vec<IMulticall3::Call3>,
+}
+
+impl Default for BatchedReads {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BatchedReads {
+    pub fn new() -> Self {
+        // The block number always comes first, so results know which block they're from
+        let mut reads = Self {
+            multicall: MULTICALL3_ADDRESS,
+            calls: Vec::new(),
+        };
+        reads.add(MULTICALL3_ADDRESS, IMulticall3::getBlockNumberCall {});
+        reads
+    }
+
+    pub fn add<C: SolCall>(&mut self, target: Address, call: C) -> BatchSlot<C> {
+        self.calls.push(IMulticall3::Call3 {
+            target,
+            // A revert fails that slot only; `BatchResults::get` reports it
+            allowFailure: true,
+            callData: Bytes::from(call.abi_encode()),
+        });
+        BatchSlot {
+            index: self.calls.len() - 1,
+            _call: PhantomData,
+        }
+    }
+
+    pub fn add_block_timestamp(&mut self) -> BatchSlot<IMulticall3::getCurrentBlockTimestampCall> {
+        self.add(self.multicall, IMulticall3::getCurrentBlockTimestampCall {})
+    }
+
+    // At `block`, or the latest block (the quorum's common head with quorum reads) when None
+    pub async fn execute(
+        self,
+        client: &BlockchainClient,
+        provider: &Arc<dyn Provider<Ethereum>>,
+        label: &str,
+        block: Option<u64>,
+    ) -> Result<BatchResults> {
+        let call = IMulticall3::aggregate3Call { calls: self.calls };
+        let tx = TransactionRequest {
+            to: Some(TxKind::Call(self.multicall)),
+            input: TransactionInput::new(Bytes::from(call.abi_encode())),
+            ..Default::default()
+        };
+        let result = critical_call_at(client, provider, label, tx, block).await?;
+        if result.is_empty() {
+            return Err(MulticallUnavailable {
+                address: self.multicall,
+                label: label.to_string(),
+            }
+            .into());
+        }
+
+        let results = IMulticall3::aggregate3Call::abi_decode_returns(&result)?;
+        let block_number = decode::<IMulticall3::getBlockNumberCall>(&results[0])?.saturating_to();
+        Ok(BatchResults {
+            results,
+            block_number,
+        })
+    }
+}
+
+pub struct BatchResults {
+    results: Vec<IMulticall3::Result>,
+    block_number: u64,
+}
+
+impl BatchResults {
+    // The block every answer was read at
+    pub fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    pub fn get<C: SolCall>(&self, slot: &BatchSlot<C>) -> Result<C::Return> {
+        decode::<C>(&self.results[slot.index])
+    }
+}
+
+fn decode<C: SolCall>(result: &IMulticall3::Result) -> Result<C::Return> {
+    if !result.success {
+        return Err(KeeperError::Rpc(format!("Batched read {} reverted", C::SIGNATURE)).into());
+    }
+    Ok(C::abi_decode_returns(&result.returnData)?)
+}
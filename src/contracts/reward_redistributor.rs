@@ -1,4 +1,6 @@
 use crate::blockchain::BlockchainClient;
+use crate::contracts::multicall3::BatchedReads;
+use crate::quorum::critical_call;
 use alloy::network::Ethereum;
 use alloy::primitives::{Address, Bytes, TxKind, B256, U256};
 use alloy::providers::Provider;
@@ -14,6 +16,10 @@ pub struct TxOverrides {
     pub nonce: Option<u64>,
     pub max_priority_fee_per_gas: Option<u128>,
     pub max_fee_per_gas: Option<u128>,
+    // Legacy chains; takes the place of the EIP-1559 fees
+    pub gas_price: Option<u128>,
+    // Skips estimation, for transactions signed before the state they depend on exists
+    pub gas_limit: Option<u64>,
 }
 
 sol! {
@@ -39,6 +45,18 @@ sol! {
     }
 }
 
+// Everything distribute-rewards checks before deciding on a snapshot, read at one block
+#[derive(Debug, Clone)]
+pub struct SnapshotState {
+    pub last_snapshot_timestamp: U256,
+    pub last_snapshot_block: U256,
+    pub snapshot_max_age: U256,
+    pub last_susdsc_tvl: U256,
+    pub last_earn_tvl: U256,
+    pub block_number: u64,
+    pub block_timestamp: U256,
+}
+
 #[derive(Clone)]
 pub struct RewardRedistributorContract {
     address: Address,
@@ -65,14 +83,17 @@ impl RewardRedistributorContract {
         let call = IRewardRedistributor::previewDistributeCall {};
         let data: Vec<u8> = call.abi_encode();
 
-        let result = self
-            .provider
-            .call(alloy::rpc::types::TransactionRequest {
+        let result = critical_call(
+            &self.client,
+            &self.provider,
+            "previewDistribute",
+            alloy::rpc::types::TransactionRequest {
                 to: Some(TxKind::Call(self.address)),
                 input: TransactionInput::new(Bytes::from(data)),
                 ..Default::default()
-            })
-            .await?;
+            },
+        )
+        .await?;
 
         // Decode the 8-tuple return type using Alloy's ABI decoder
         let decoded = IRewardRedistributor::previewDistributeCall::abi_decode_returns(&result)?;
@@ -90,49 +111,65 @@ impl RewardRedistributorContract {
     }
 
     // Distribute functions
-    pub async fn distribute(&self, value_wei: &str, overrides: TxOverrides) -> Result<B256> {
+    pub fn distribute_request(
+        &self,
+        value_wei: &str,
+        overrides: TxOverrides,
+    ) -> Result<TransactionRequest> {
         let call = IRewardRedistributor::distributeCall {};
-        let data: Vec<u8> = call.abi_encode();
-
-        let tx_value = U256::from_str(value_wei)?;
+        self.request(call.abi_encode(), value_wei, overrides)
+    }
 
-        let tx = TransactionRequest {
-            to: Some(TxKind::Call(self.address)),
-            input: TransactionInput::new(data.into()),
-            value: Some(tx_value),
-            nonce: overrides.nonce,
-            max_priority_fee_per_gas: overrides.max_priority_fee_per_gas,
-            max_fee_per_gas: overrides.max_fee_per_gas,
-            ..Default::default()
-        };
+    pub async fn distribute(&self, value_wei: &str, overrides: TxOverrides) -> Result<B256> {
+        let tx = self.distribute_request(value_wei, overrides)?;
 
-        let tx_hash = self.client.send_transaction(tx).await?;
+        let tx_hash = self.client.send_transaction("distribute", tx).await?;
         Ok(tx_hash)
     }
 
+    pub fn snapshot_vault_tvls_request(
+        &self,
+        value_wei: &str,
+        overrides: TxOverrides,
+    ) -> Result<TransactionRequest> {
+        let call = IRewardRedistributor::snapshotVaultTVLsCall {};
+        self.request(call.abi_encode(), value_wei, overrides)
+    }
+
     // Snapshot: captures sUSDSC vault TVL and EarnVault totalPrincipal for distribution split
     pub async fn snapshot_vault_tvls(
         &self,
         value_wei: &str,
         overrides: TxOverrides,
     ) -> Result<B256> {
-        let call = IRewardRedistributor::snapshotVaultTVLsCall {};
-        let data: Vec<u8> = call.abi_encode();
+        let tx = self.snapshot_vault_tvls_request(value_wei, overrides)?;
+
+        let tx_hash = self
+            .client
+            .send_transaction("snapshotVaultTVLs", tx)
+            .await?;
+        Ok(tx_hash)
+    }
 
+    fn request(
+        &self,
+        data: Vec<u8>,
+        value_wei: &str,
+        overrides: TxOverrides,
+    ) -> Result<TransactionRequest> {
         let tx_value = U256::from_str(value_wei)?;
 
-        let tx = TransactionRequest {
+        Ok(TransactionRequest {
             to: Some(TxKind::Call(self.address)),
             input: TransactionInput::new(data.into()),
             value: Some(tx_value),
             nonce: overrides.nonce,
             max_priority_fee_per_gas: overrides.max_priority_fee_per_gas,
             max_fee_per_gas: overrides.max_fee_per_gas,
+            gas_price: overrides.gas_price,
+            gas: overrides.gas_limit,
             ..Default::default()
-        };
-
-        let tx_hash = self.client.send_transaction(tx).await?;
-        Ok(tx_hash)
+        })
     }
 
     pub async fn last_susdsc_tvl(&self) -> Result<U256> {
@@ -173,14 +210,17 @@ impl RewardRedistributorContract {
         let call = IRewardRedistributor::lastSnapshotTimestampCall {};
         let data: Vec<u8> = call.abi_encode();
 
-        let result = self
-            .provider
-            .call(alloy::rpc::types::TransactionRequest {
+        let result = critical_call(
+            &self.client,
+            &self.provider,
+            "lastSnapshotTimestamp",
+            alloy::rpc::types::TransactionRequest {
                 to: Some(TxKind::Call(self.address)),
                 input: TransactionInput::new(Bytes::from(data)),
                 ..Default::default()
-            })
-            .await?;
+            },
+        )
+        .await?;
 
         let decoded = IRewardRedistributor::lastSnapshotTimestampCall::abi_decode_returns(&result)?;
         Ok(decoded)
@@ -190,14 +230,17 @@ impl RewardRedistributorContract {
         let call = IRewardRedistributor::lastSnapshotBlockNumberCall {};
         let data: Vec<u8> = call.abi_encode();
 
-        let result = self
-            .provider
-            .call(alloy::rpc::types::TransactionRequest {
+        let result = critical_call(
+            &self.client,
+            &self.provider,
+            "lastSnapshotBlockNumber",
+            alloy::rpc::types::TransactionRequest {
                 to: Some(TxKind::Call(self.address)),
                 input: TransactionInput::new(Bytes::from(data)),
                 ..Default::default()
-            })
-            .await?;
+            },
+        )
+        .await?;
 
         let decoded =
             IRewardRedistributor::lastSnapshotBlockNumberCall::abi_decode_returns(&result)?;
@@ -220,4 +263,35 @@ impl RewardRedistributorContract {
         let decoded = IRewardRedistributor::snapshotMaxAgeCall::abi_decode_returns(&result)?;
         Ok(decoded)
     }
+
+    // Batched variant of the snapshot getters, with the block number and timestamp they
+    // were read at
+    pub async fn snapshot_state(&self, block: Option<u64>) -> Result<SnapshotState> {
+        let mut reads = BatchedReads::new();
+        let last_snapshot_timestamp = reads.add(
+            self.address,
+            IRewardRedistributor::lastSnapshotTimestampCall {},
+        );
+        let last_snapshot_block = reads.add(
+            self.address,
+            IRewardRedistributor::lastSnapshotBlockNumberCall {},
+        );
+        let snapshot_max_age = reads.add(self.address, IRewardRedistributor::snapshotMaxAgeCall {});
+        let last_susdsc_tvl = reads.add(self.address, IRewardRedistributor::lastSusdscTVLCall {});
+        let last_earn_tvl = reads.add(self.address, IRewardRedistributor::lastEarnTVLCall {});
+        let block_timestamp = reads.add_block_timestamp();
+
+        let results = reads
+            .execute(&self.client, &self.provider, "snapshot state", block)
+            .await?;
+        Ok(SnapshotState {
+            last_snapshot_timestamp: results.get(&last_snapshot_timestamp)?,
+            last_snapshot_block: results.get(&last_snapshot_block)?,
+            snapshot_max_age: results.get(&snapshot_max_age)?,
+            last_susdsc_tvl: results.get(&last_susdsc_tvl)?,
+            last_earn_tvl: results.get(&last_earn_tvl)?,
+            block_number: results.block_number(),
+            block_timestamp: results.get(&block_timestamp)?,
+        })
+    }
 }
@@ -1,4 +1,6 @@
 use crate::blockchain::BlockchainClient;
+use crate::contracts::multicall3::{BatchSlot, BatchedReads};
+use crate::quorum::critical_call;
 use alloy::network::Ethereum;
 use alloy::primitives::{Address, Bytes, TxKind, B256, U256};
 use alloy::providers::Provider;
@@ -40,34 +42,48 @@ impl USDSCContract {
         let call = IUSDSC::r#yieldCall {};
         let data: Vec<u8> = call.abi_encode();
 
-        let result = self
-            .provider
-            .call(alloy::rpc::types::TransactionRequest {
+        let result = critical_call(
+            &self.client,
+            &self.provider,
+            "yield",
+            alloy::rpc::types::TransactionRequest {
                 to: Some(TxKind::Call(self.address)),
                 input: TransactionInput::new(Bytes::from(data)),
                 ..Default::default()
-            })
-            .await?;
+            },
+        )
+        .await?;
 
         let yield_amount = U256::from_be_slice(&result);
         Ok(yield_amount)
     }
 
-    pub async fn claim_yield(&self, value_wei: &str) -> Result<B256> {
+    // Batched variant of `get_pending_yield`, for reading it together with other calls
+    #[allow(dead_code)] // Used in tests and public API
+    pub fn add_pending_yield(&self, reads: &mut BatchedReads) -> BatchSlot<IUSDSC::yieldCall> {
+        reads.add(self.address, IUSDSC::r#yieldCall {})
+    }
+
+    // The claimYield transaction, also used to estimate its cost
+    pub fn claim_yield_request(&self, value_wei: &str) -> Result<TransactionRequest> {
         let call = IUSDSC::claimYieldCall {};
         let data: Vec<u8> = call.abi_encode();
 
         let tx_value = U256::from_str(value_wei)?;
 
-        let tx = TransactionRequest {
+        Ok(TransactionRequest {
             to: Some(TxKind::Call(self.address)),
             input: TransactionInput::new(data.into()),
             value: Some(tx_value),
             ..Default::default()
-        };
+        })
+    }
+
+    pub async fn claim_yield(&self, value_wei: &str) -> Result<B256> {
+        let tx = self.claim_yield_request(value_wei)?;
 
         // Use the unified transaction sending (works for both private key and KMS)
-        let tx_hash = self.client.send_transaction(tx).await?;
+        let tx_hash = self.client.send_transaction("claimYield", tx).await?;
         Ok(tx_hash)
     }
 }
@@ -0,0 +1,394 @@
+use crate::config::ChainConfig;
+use crate::jobs::boost_rewards_s3::BoostRewardsS3;
+use crate::jobs::{ClaimYieldJob, DistributeRewardsJob, JobContext, JobOutcome};
+use crate::metrics::metrics;
+use crate::notifier::{Alert, AlertKind, Notifier};
+use crate::report::RunRecorder;
+use crate::run_lock::{RunLock, RunLockGuard};
+use crate::server::KeeperStatus;
+use crate::sources::s3_campaign_source::S3CampaignSource;
+use anyhow::Result;
+use chrono::Utc;
+use cron::Schedule;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::{watch, Mutex, OnceCell};
+use tokio::task::JoinSet;
+use tracing::{error, info, info_span, warn, Instrument};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JobKind {
+    ClaimYield,
+    DistributeRewards,
+    BoostRewardsS3,
+}
+
+impl JobKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobKind::ClaimYield => "claim-yield",
+            JobKind::DistributeRewards => "distribute-rewards",
+            JobKind::BoostRewardsS3 => "boost-rewards-s3",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ScheduleFile {
+    pub jobs: Vec<ScheduledJob>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ScheduledJob {
+    pub name: String,
+    pub job: JobKind,
+    // Standard 5-field cron expression (UTC); a leading seconds field is also accepted
+    pub schedule: String,
+    pub config: String,
+    #[serde(default)]
+    pub dry_run: bool,
+    pub campaigns_s3: Option<String>,
+    pub s3_region: Option<String>,
+}
+
+impl ScheduleFile {
+    pub fn load(path: &str) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read schedule file {}: {}", path, e))?;
+        // Same ${VAR} substitution as chain configs
+        let content = ChainConfig::substitute_env_vars(content)?;
+        let schedule: ScheduleFile = toml::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse schedule file {}: {}", path, e))?;
+
+        if schedule.jobs.is_empty() {
+            return Err(anyhow::anyhow!("Schedule file {} has no jobs", path));
+        }
+
+        let mut names = std::collections::HashSet::new();
+        for job in &schedule.jobs {
+            if !names.insert(job.name.as_str()) {
+                return Err(anyhow::anyhow!(
+                    "Duplicate scheduled job name: {}",
+                    job.name
+                ));
+            }
+            job.cron_schedule()?;
+            if job.job == JobKind::BoostRewardsS3 && job.campaigns_s3.is_none() {
+                return Err(anyhow::anyhow!(
+                    "Scheduled job {} ({}) requires campaigns_s3",
+                    job.name,
+                    job.job.as_str()
+                ));
+            }
+        }
+
+        Ok(schedule)
+    }
+}
+
+impl ScheduledJob {
+    pub fn cron_schedule(&self) -> Result<Schedule> {
+        // The cron crate expects a seconds field; add one for standard expressions
+        let expression = if self.schedule.split_whitespace().count() == 5 {
+            format!("0 {}", self.schedule)
+        } else {
+            self.schedule.clone()
+        };
+        Schedule::from_str(&expression).map_err(|e| {
+            anyhow::anyhow!(
+                "Invalid cron expression '{}' for job {}: {}",
+                self.schedule,
+                self.name,
+                e
+            )
+        })
+    }
+}
+
+struct DaemonEntry {
+    job: ScheduledJob,
+    schedule: Schedule,
+    config: ChainConfig,
+    context: Arc<OnceCell<Arc<JobContext>>>,
+    notifier: Arc<Notifier>,
+    // Shared by entries that take the same run lock, so they queue instead of failing
+    // each other with LockHeld
+    run_lock: Arc<Mutex<()>>,
+    status: KeeperStatus,
+}
+
+// Runs scheduled jobs in one long-lived process. Jobs that share a config file share
+// one JobContext (and notifier), so KMS and RPC setup happens once per chain and alerts
+// are deduplicated across runs. Jobs that share a run lock run one at a time.
+pub struct Daemon {
+    entries: Vec<DaemonEntry>,
+}
+
+impl Daemon {
+    pub fn new(jobs: Vec<(ScheduledJob, ChainConfig)>, status: KeeperStatus) -> Result<Self> {
+        type Shared = (Arc<OnceCell<Arc<JobContext>>>, Arc<Notifier>);
+        let mut shared: HashMap<String, Shared> = HashMap::new();
+        let mut run_locks: HashMap<String, Arc<Mutex<()>>> = HashMap::new();
+        let mut entries = Vec::new();
+
+        for (job, config) in jobs {
+            let schedule = job.cron_schedule()?;
+            let (context, notifier) = shared
+                .entry(job.config.clone())
+                .or_insert_with(|| {
+                    (
+                        Arc::new(OnceCell::new()),
+                        Arc::new(Notifier::from_config(&config)),
+                    )
+                })
+                .clone();
+            let run_lock = run_locks
+                .entry(RunLock::lock_name(&config))
+                .or_default()
+                .clone();
+            entries.push(DaemonEntry {
+                job,
+                schedule,
+                config,
+                context,
+                notifier,
+                run_lock,
+                status: status.clone(),
+            });
+        }
+
+        Ok(Self { entries })
+    }
+
+    pub async fn run(self) -> Result<()> {
+        info!(
+            "🕰️  Keeper daemon starting with {} scheduled job(s)",
+            self.entries.len()
+        );
+
+        self.connect_all().await;
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let mut tasks = JoinSet::new();
+        for entry in self.entries {
+            info!(
+                "   {} ({}) on '{}' with {}",
+                entry.job.name,
+                entry.job.job.as_str(),
+                entry.job.schedule,
+                entry.job.config
+            );
+            tasks.spawn(run_entry(entry, shutdown_rx.clone()));
+        }
+
+        wait_for_shutdown_signal().await;
+        info!("🛑 Shutdown requested, waiting for running jobs to finish...");
+        let _ = shutdown_tx.send(true);
+
+        while let Some(result) = tasks.join_next().await {
+            if let Err(e) = result {
+                error!("❌ Scheduler task panicked: {}", e);
+            }
+        }
+
+        info!("👋 Keeper daemon stopped");
+        Ok(())
+    }
+
+    // Connect every chain up front so readiness reflects all of them before the first
+    // tick; failures are logged and retried on the job's first run
+    async fn connect_all(&self) {
+        for entry in &self.entries {
+            if entry.context.initialized() {
+                continue;
+            }
+            if let Err(e) = entry.context().await {
+                warn!(
+                    "⚠️  Could not connect {} yet, will retry on first run: {}",
+                    entry.job.config, e
+                );
+            }
+        }
+    }
+}
+
+// Each entry runs its job inline, so a run can never overlap the previous one; ticks
+// that pass while a run is in progress are skipped.
+async fn run_entry(entry: DaemonEntry, mut shutdown: watch::Receiver<bool>) {
+    loop {
+        let Some(next_tick) = entry.schedule.upcoming(Utc).next() else {
+            warn!("⚠️  {} has no upcoming runs, stopping", entry.job.name);
+            return;
+        };
+        let wait = (next_tick - Utc::now()).to_std().unwrap_or_default();
+
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = shutdown.changed() => return,
+        }
+        if *shutdown.borrow() {
+            return;
+        }
+
+        info!(
+            "⏰ Running scheduled job {} ({})",
+            entry.job.name, next_tick
+        );
+        let span = info_span!(
+            "job_run",
+            job = %entry.job.name,
+            kind = entry.job.job.as_str(),
+            chain_id = entry.config.chain.chain_id,
+            dry_run = entry.job.dry_run
+        );
+        match entry.run_once().instrument(span).await {
+            Ok(()) => info!("✅ Scheduled job {} finished", entry.job.name),
+            Err(e) => error!("❌ Scheduled job {} failed: {}", entry.job.name, e),
+        }
+
+        let now = Utc::now();
+        let skipped = entry
+            .schedule
+            .after(&next_tick)
+            .take_while(|tick| *tick <= now)
+            .count();
+        if skipped > 0 {
+            warn!(
+                "⚠️  {} overran its schedule, skipped {} tick(s)",
+                entry.job.name, skipped
+            );
+        }
+    }
+}
+
+impl DaemonEntry {
+    async fn run_once(&self) -> Result<()> {
+        // Dry runs never send, so only real runs take the shared run lock
+        if self.job.dry_run {
+            return self.run_job(None).await;
+        }
+
+        // Jobs of this daemon on the same chain and key wait for each other; the run
+        // lock itself only fails fast against other processes
+        let _serialized = match self.run_lock.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => {
+                info!(
+                    "⏳ {} waits for another job on the same chain and key",
+                    self.job.name
+                );
+                self.run_lock.lock().await
+            }
+        };
+        let lock = RunLock::acquire_for_job(&self.config, &self.job.name).await?;
+        let result = self.run_job(Some(&lock)).await;
+        lock.release().await;
+        result
+    }
+
+    async fn context(&self) -> Result<&Arc<JobContext>> {
+        let ctx = self
+            .context
+            .get_or_try_init(|| async {
+                let ctx = JobContext::connect(&self.config).await?;
+                Ok::<_, anyhow::Error>(Arc::new(ctx.with_notifier(self.notifier.clone())))
+            })
+            .await?;
+        self.status.register_context(ctx.clone());
+        Ok(ctx)
+    }
+
+    async fn run_job(&self, lock: Option<&RunLockGuard>) -> Result<()> {
+        self.status.run_started(&self.job.name);
+        let started = std::time::Instant::now();
+        let report = RunRecorder::new(&self.job.name, &self.config, self.job.dry_run);
+        let result = match lock {
+            Some(lock) => lock.guard(self.execute_job(&report)).await,
+            None => self.execute_job(&report).await,
+        }
+        .map(|_| ());
+        report.finish(&result).write(&self.config).await;
+        self.status.run_finished(&self.job.name, &result);
+        metrics().record_job_run(&self.job.name, result.is_ok(), started.elapsed());
+        if let Err(e) = &result {
+            self.notifier
+                .notify(Alert::new(
+                    AlertKind::JobFailed,
+                    self.config.chain.chain_id,
+                    &self.job.name,
+                    format!("{} failed: {}", self.job.name, e),
+                ))
+                .await;
+        }
+        if let Some(ctx) = self.context.get() {
+            ctx.check_native_balance().await;
+        }
+        result
+    }
+
+    async fn execute_job(&self, report: &RunRecorder) -> Result<JobOutcome> {
+        let ctx = self.context().await?;
+        if let Some(reason) = ctx.check_liveness().await? {
+            report.decision(format!("skipped: chain stalled ({})", reason));
+            return Ok(JobOutcome::ChainStalled);
+        }
+        if !self.job.dry_run {
+            ctx.reconcile_journal().await?;
+        }
+
+        match self.job.job {
+            JobKind::ClaimYield => {
+                ClaimYieldJob::new(self.config.clone(), self.job.dry_run)
+                    .with_report(report.clone())
+                    .execute(ctx)
+                    .await
+            }
+            JobKind::DistributeRewards => {
+                DistributeRewardsJob::new(self.config.clone(), self.job.dry_run)
+                    .with_report(report.clone())
+                    .execute(ctx)
+                    .await
+            }
+            JobKind::BoostRewardsS3 => {
+                let campaigns_s3 = self.job.campaigns_s3.as_deref().ok_or_else(|| {
+                    anyhow::anyhow!("campaigns_s3 not configured for {}", self.job.name)
+                })?;
+                let s3_region =
+                    S3CampaignSource::resolve_region(self.job.s3_region.clone(), &self.config)?;
+                let campaign_source =
+                    Box::new(S3CampaignSource::from_uri(campaigns_s3, &s3_region).await?);
+                let summaries = BoostRewardsS3::new(self.config.clone(), campaign_source)
+                    .with_report(report.clone())
+                    .run(ctx)
+                    .await?;
+                BoostRewardsS3::outcome(&summaries)
+            }
+        }
+    }
+}
+
+async fn wait_for_shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = sigterm.recv() => {}
+                }
+            }
+            Err(_) => {
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
@@ -0,0 +1,114 @@
+use alloy::transports::TransportError;
+use thiserror::Error;
+
+// Process exit codes. 1 stays the catch-all for unclassified errors; 4-6 are worth
+// retrying on the next schedule, 7 and up need a human.
+pub const EXIT_SUCCESS: u8 = 0;
+pub const EXIT_UNCLASSIFIED: u8 = 1;
+pub const EXIT_CONFIG: u8 = 2;
+pub const EXIT_NO_OP: u8 = 3;
+pub const EXIT_RPC: u8 = 4;
+pub const EXIT_LOCK_HELD: u8 = 5;
+pub const EXIT_TX_TIMEOUT: u8 = 6;
+pub const EXIT_KMS: u8 = 7;
+pub const EXIT_TX_REVERTED: u8 = 8;
+pub const EXIT_INSUFFICIENT_FUNDS: u8 = 9;
+pub const EXIT_CAMPAIGNS_FAILED: u8 = 10;
+pub const EXIT_QUORUM_DISAGREEMENT: u8 = 11;
+pub const EXIT_CHAIN_STALLED: u8 = 12;
+pub const EXIT_DEFERRED: u8 = 13;
+
+// Failure classes the keeper distinguishes. They travel inside `anyhow::Error`, so
+// call sites keep using `?` and the class is recovered with `KeeperError::find`.
+#[derive(Debug, Clone, Error)]
+pub enum KeeperError {
+    #[error("Configuration error: {0}")]
+    Config(String),
+    #[error("RPC error: {0}")]
+    Rpc(String),
+    #[error("KMS error: {0}")]
+    Kms(String),
+    #[error("{0}")]
+    LockHeld(String),
+    #[error("{0}")]
+    TransactionTimeout(String),
+    #[error("{0}")]
+    TransactionReverted(String),
+    #[error("Insufficient funds: {0}")]
+    InsufficientFunds(String),
+    #[error("{failed} of {total} campaigns failed")]
+    CampaignsFailed { failed: usize, total: usize },
+    #[error("Quorum endpoints disagree: {0}")]
+    QuorumDisagreement(String),
+    // Signed and maybe broadcast, but the node never confirmed it. Only the same signed
+    // bytes may be sent again, so the journal reconcile settles it, not a retry.
+    #[error("Transaction may have been broadcast: {0}")]
+    SendUncertain(String),
+}
+
+impl KeeperError {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            KeeperError::Config(_) => EXIT_CONFIG,
+            KeeperError::Rpc(_) => EXIT_RPC,
+            KeeperError::Kms(_) => EXIT_KMS,
+            KeeperError::LockHeld(_) => EXIT_LOCK_HELD,
+            KeeperError::TransactionTimeout(_) => EXIT_TX_TIMEOUT,
+            KeeperError::TransactionReverted(_) => EXIT_TX_REVERTED,
+            KeeperError::InsufficientFunds(_) => EXIT_INSUFFICIENT_FUNDS,
+            KeeperError::CampaignsFailed { .. } => EXIT_CAMPAIGNS_FAILED,
+            KeeperError::QuorumDisagreement(_) => EXIT_QUORUM_DISAGREEMENT,
+            KeeperError::SendUncertain(_) => EXIT_RPC,
+        }
+    }
+
+    // Retrying the same call can't fix these (or, for an uncertain send, would sign a
+    // second tx), so execute_with_retry gives up at once
+    pub fn is_permanent(&self) -> bool {
+        matches!(
+            self,
+            KeeperError::Config(_)
+                | KeeperError::TransactionReverted(_)
+                | KeeperError::InsufficientFunds(_)
+                | KeeperError::QuorumDisagreement(_)
+                | KeeperError::SendUncertain(_)
+        )
+    }
+
+    // First classified error in the chain
+    pub fn find(err: &anyhow::Error) -> Option<&KeeperError> {
+        err.chain()
+            .find_map(|cause| cause.downcast_ref::<KeeperError>())
+    }
+}
+
+// Exit code for a failed run. Raw transport errors that were never classified still
+// count as RPC failures.
+pub fn exit_code(err: &anyhow::Error) -> u8 {
+    if let Some(classified) = KeeperError::find(err) {
+        return classified.exit_code();
+    }
+    if err
+        .chain()
+        .any(|cause| cause.downcast_ref::<TransportError>().is_some())
+    {
+        return EXIT_RPC;
+    }
+    EXIT_UNCLASSIFIED
+}
+
+// Errors returned by the node when submitting a transaction
+pub fn classify_send_error(err: TransportError) -> KeeperError {
+    let message = err.to_string();
+    let lower = message.to_lowercase();
+    if lower.contains("insufficient funds") {
+        KeeperError::InsufficientFunds(message)
+    } else if lower.contains("execution reverted") {
+        KeeperError::TransactionReverted(message)
+    } else if matches!(err, TransportError::LocalUsageError(_)) {
+        // The only local step of a send is signing, which goes through KMS
+        KeeperError::Kms(message)
+    } else {
+        KeeperError::Rpc(message)
+    }
+}
@@ -0,0 +1,254 @@
+use crate::config::{ChainConfig, GasPricing, TipStrategy, TransactionSettings};
+use crate::error::KeeperError;
+use alloy::eips::BlockNumberOrTag;
+use alloy::network::Ethereum;
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use anyhow::Result;
+use tracing::{info, warn};
+
+const GWEI: f64 = 1_000_000_000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeQuote {
+    pub base_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+    pub max_fee_per_gas: u128,
+    // The cap cut the fee below base fee * multiplier + tip
+    pub capped: bool,
+    // Pre-EIP-1559 gas price: `max_fee_per_gas` is the gas price, the base fee field
+    // holds eth_gasPrice and there is no tip
+    pub legacy: bool,
+}
+
+impl FeeQuote {
+    // Sets `gasPrice` for legacy quotes, the EIP-1559 fee fields otherwise
+    pub fn apply(&self, tx: &mut TransactionRequest) {
+        if self.legacy {
+            tx.gas_price = Some(self.max_fee_per_gas);
+        } else {
+            tx.max_fee_per_gas = Some(self.max_fee_per_gas);
+            tx.max_priority_fee_per_gas = Some(self.max_priority_fee_per_gas);
+        }
+    }
+
+    // What a transaction is expected to pay per gas: base fee + tip, or the gas price
+    pub fn expected_gas_price(&self) -> u128 {
+        if self.legacy {
+            self.max_fee_per_gas
+        } else {
+            self.base_fee_per_gas + self.max_priority_fee_per_gas
+        }
+    }
+}
+
+// EIP-1559 fees for every transaction the keeper sends:
+// max fee = next base fee * multiplier + tip, never above `max_fee_per_gas_gwei`.
+// Chains without a base fee get eth_gasPrice * `gas_price_multiplier` under the same cap.
+#[derive(Debug, Clone)]
+pub struct FeeStrategy {
+    pricing: GasPricing,
+    tip: TipStrategy,
+    fixed_tip_wei: Option<u128>,
+    percentile: f64,
+    history_blocks: u64,
+    base_fee_multiplier: f64,
+    gas_price_multiplier: f64,
+    max_fee_cap_wei: Option<u128>,
+    max_total_fee_wei: Option<u128>,
+}
+
+impl FeeStrategy {
+    pub fn from_settings(settings: &TransactionSettings) -> Self {
+        Self {
+            pricing: GasPricing::default(),
+            tip: settings.tip_strategy,
+            fixed_tip_wei: settings.max_priority_fee_gwei.map(gwei_to_wei),
+            percentile: settings.fee_history_percentile.clamp(0.0, 100.0),
+            history_blocks: settings.fee_history_blocks.max(1),
+            base_fee_multiplier: settings.base_fee_multiplier.max(1.0),
+            gas_price_multiplier: settings.gas_price_multiplier.max(1.0),
+            max_fee_cap_wei: settings.max_fee_per_gas_gwei.map(gwei_to_wei),
+            max_total_fee_wei: settings.max_total_fee_eth.map(|eth| (eth * 1e18) as u128),
+        }
+    }
+
+    // Transaction settings plus the chain's `gas_pricing` mode
+    pub fn from_config(config: &ChainConfig) -> Self {
+        Self::from_settings(&config.transaction).with_pricing(config.chain.gas_pricing)
+    }
+
+    pub fn with_pricing(mut self, pricing: GasPricing) -> Self {
+        self.pricing = pricing;
+        self
+    }
+
+    pub fn pricing(&self) -> GasPricing {
+        self.pricing
+    }
+
+    // Applies the multiplier and the cap. The tip never exceeds the max fee.
+    pub fn quote(&self, base_fee_per_gas: u128, tip: u128) -> FeeQuote {
+        let uncapped = (base_fee_per_gas as f64 * self.base_fee_multiplier) as u128 + tip;
+        let (max_fee_per_gas, capped) = match self.max_fee_cap_wei {
+            Some(cap) if uncapped > cap => (cap, true),
+            _ => (uncapped, false),
+        };
+        FeeQuote {
+            base_fee_per_gas,
+            max_priority_fee_per_gas: tip.min(max_fee_per_gas),
+            max_fee_per_gas,
+            capped,
+            legacy: false,
+        }
+    }
+
+    // Legacy gas price: eth_gasPrice * multiplier, under the same cap
+    pub fn legacy_quote(&self, network_gas_price: u128) -> FeeQuote {
+        let uncapped = (network_gas_price as f64 * self.gas_price_multiplier) as u128;
+        let (gas_price, capped) = match self.max_fee_cap_wei {
+            Some(cap) if uncapped > cap => (cap, true),
+            _ => (uncapped, false),
+        };
+        FeeQuote {
+            base_fee_per_gas: network_gas_price,
+            max_priority_fee_per_gas: 0,
+            max_fee_per_gas: gas_price,
+            capped,
+            legacy: true,
+        }
+    }
+
+    // Lowers the max fee (or gas price) so gas * max fee + L1 data fee stays within the per-transaction
+    // total. Fails when the L1 data fee alone uses up the budget, or when what is left would
+    // price the transaction below the current base fee, where it could never be included.
+    pub fn fit_total_fee(
+        &self,
+        max_fee_per_gas: u128,
+        gas: u64,
+        l1_fee: u128,
+        base_fee_per_gas: Option<u128>,
+    ) -> Result<u128> {
+        let Some(total) = self.max_total_fee_wei else {
+            return Ok(max_fee_per_gas);
+        };
+        let budget = total.checked_sub(l1_fee).filter(|budget| *budget > 0);
+        let Some(budget) = budget else {
+            return Err(anyhow::anyhow!(
+                "L1 data fee {} wei exceeds the {} wei per-transaction fee cap",
+                l1_fee,
+                total
+            ));
+        };
+        let fitted = max_fee_per_gas.min(budget / u128::from(gas.max(1)));
+        if let Some(base_fee) = base_fee_per_gas.filter(|base_fee| fitted < *base_fee) {
+            return Err(anyhow::anyhow!(
+                "Max fee {} gwei left after the {} wei L1 data fee is below the current base fee {} gwei",
+                wei_to_gwei(fitted),
+                l1_fee,
+                wei_to_gwei(base_fee)
+            ));
+        }
+        Ok(fitted)
+    }
+
+    // One eth_feeHistory call gives the next block's base fee and the recent tips. In
+    // auto mode a chain reporting no base fee falls back to the legacy gas price.
+    pub async fn estimate(&self, provider: &dyn Provider<Ethereum>) -> Result<FeeQuote> {
+        if self.pricing == GasPricing::Legacy {
+            return self.estimate_legacy(provider).await;
+        }
+        let fixed_tip = match self.tip {
+            TipStrategy::Fixed => self.fixed_tip_wei,
+            TipStrategy::FeeHistory => None,
+        };
+        let (blocks, percentiles) = match fixed_tip {
+            Some(_) => (1, Vec::new()),
+            None => (self.history_blocks, vec![self.percentile]),
+        };
+        let history = provider
+            .get_fee_history(blocks, BlockNumberOrTag::Latest, &percentiles)
+            .await
+            .map_err(|e| KeeperError::Rpc(format!("eth_feeHistory failed: {}", e)))?;
+        let base_fee = history
+            .base_fee_per_gas
+            .last()
+            .copied()
+            .filter(|fee| *fee > 0);
+        let base_fee = match base_fee {
+            Some(base_fee) => base_fee,
+            None if self.pricing == GasPricing::Auto => {
+                return self.estimate_legacy(provider).await;
+            }
+            None => return Err(anyhow::anyhow!("No baseFeePerGas (pre-EIP-1559 chain)")),
+        };
+
+        let tip = match fixed_tip {
+            Some(tip) => tip,
+            None => {
+                let mut rewards: Vec<u128> = history
+                    .reward
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|block| block.first().copied())
+                    .collect();
+                rewards.sort_unstable();
+                // Median over the window so one outlier block doesn't set the tip
+                match rewards.get(rewards.len() / 2) {
+                    Some(tip) => *tip,
+                    None => provider
+                        .get_max_priority_fee_per_gas()
+                        .await
+                        .map_err(|e| KeeperError::Rpc(e.to_string()))?,
+                }
+            }
+        };
+
+        let quote = self.quote(base_fee, tip);
+        info!(
+            "⛽ Fees: base {} gwei, tip {} gwei, max {} gwei",
+            wei_to_gwei(quote.base_fee_per_gas),
+            wei_to_gwei(quote.max_priority_fee_per_gas),
+            wei_to_gwei(quote.max_fee_per_gas)
+        );
+        if quote.capped {
+            warn!(
+                "⚠️  Max fee capped at {} gwei (base fee {} gwei x {} + tip)",
+                wei_to_gwei(quote.max_fee_per_gas),
+                wei_to_gwei(base_fee),
+                self.base_fee_multiplier
+            );
+        }
+        Ok(quote)
+    }
+
+    async fn estimate_legacy(&self, provider: &dyn Provider<Ethereum>) -> Result<FeeQuote> {
+        let network_gas_price = provider
+            .get_gas_price()
+            .await
+            .map_err(|e| KeeperError::Rpc(format!("eth_gasPrice failed: {}", e)))?;
+        let quote = self.legacy_quote(network_gas_price);
+        info!(
+            "⛽ Legacy gas price: network {} gwei, paying {} gwei",
+            wei_to_gwei(network_gas_price),
+            wei_to_gwei(quote.max_fee_per_gas)
+        );
+        if quote.capped {
+            warn!(
+                "⚠️  Gas price capped at {} gwei (eth_gasPrice {} gwei x {})",
+                wei_to_gwei(quote.max_fee_per_gas),
+                wei_to_gwei(network_gas_price),
+                self.gas_price_multiplier
+            );
+        }
+        Ok(quote)
+    }
+}
+
+pub fn gwei_to_wei(gwei: f64) -> u128 {
+    (gwei * GWEI) as u128
+}
+
+pub fn wei_to_gwei(wei: u128) -> f64 {
+    wei as f64 / GWEI
+}
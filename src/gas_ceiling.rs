@@ -0,0 +1,99 @@
+use crate::config::{ChainConfig, GasCeilingSettings};
+use crate::fees::gwei_to_wei;
+use crate::run_lock::RunLock;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GasDecision {
+    Proceed,
+    // Base fee above the ceiling; try again on the next run
+    Defer { deferred_for: Duration },
+    // Still above the ceiling, but deferred for longer than allowed
+    Urgent { deferred_for: Duration },
+}
+
+// First and latest deferred run of the current streak
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Deferral {
+    pub since: DateTime<Utc>,
+    pub last: DateTime<Utc>,
+}
+
+pub fn decide(
+    ceiling: &GasCeilingSettings,
+    base_fee_wei: u128,
+    deferral: Option<Deferral>,
+    now: DateTime<Utc>,
+) -> GasDecision {
+    if base_fee_wei <= gwei_to_wei(ceiling.max_base_fee_gwei) {
+        return GasDecision::Proceed;
+    }
+    let max_deferral = ceiling.max_deferral_seconds;
+    // A streak only continues if the previous deferral was recent; one left over from
+    // before a quiet stretch (e.g. days below the yield threshold) starts over
+    let since = deferral
+        .filter(|d| max_deferral.is_none_or(|max| (now - d.last).num_seconds() <= max as i64))
+        .map_or(now, |d| d.since);
+    let deferred_for = (now - since).to_std().unwrap_or_default();
+    match max_deferral {
+        Some(max) if deferred_for.as_secs() >= max => GasDecision::Urgent { deferred_for },
+        _ => GasDecision::Defer { deferred_for },
+    }
+}
+
+// Deferral streaks per job, kept next to the journal so they survive one-shot runs
+pub struct DeferralStore {
+    path: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl DeferralStore {
+    pub fn from_config(config: &ChainConfig) -> Self {
+        Self {
+            path: config
+                .journal
+                .dir()
+                .join(format!("{}-deferrals.json", RunLock::lock_name(config))),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    fn load(&self) -> Result<BTreeMap<String, Deferral>> {
+        match fs::read_to_string(&self.path) {
+            Ok(content) => Ok(serde_json::from_str(&content).unwrap_or_default()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+            Err(e) => Err(anyhow::anyhow!(
+                "Failed to read deferrals {}: {}",
+                self.path.display(),
+                e
+            )),
+        }
+    }
+
+    pub fn get(&self, job: &str) -> Result<Option<Deferral>> {
+        Ok(self.load()?.get(job).copied())
+    }
+
+    pub fn set(&self, job: &str, deferral: Option<Deferral>) -> Result<()> {
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut all = self.load()?;
+        match deferral {
+            Some(deferral) => all.insert(job.to_string(), deferral),
+            None => all.remove(job),
+        };
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(&all)?)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
@@ -1,20 +1,31 @@
 use crate::blockchain::BlockchainClient;
-use crate::config::ChainConfig;
+use crate::config::{BoostTokenLimit, ChainConfig};
 use crate::contracts::earn_vault::EarnVaultContract;
 use crate::contracts::erc20::ERC20Contract;
-use crate::retry::{execute_with_retry, RetryConfig};
-use crate::transaction_monitor::{TransactionMonitor, TransactionStatus};
-use alloy::primitives::{Address, U256};
+use crate::contracts::multicall3::MulticallUnavailable;
+use crate::error::KeeperError;
+use crate::jobs::JobContext;
+use crate::journal::{JournalStatus, TxJournal};
+use crate::metrics::metrics;
+use crate::notifier::{Alert, AlertKind};
+use crate::report::RunRecorder;
+use crate::retry::execute_with_retry;
+use crate::transaction_monitor::TransactionStatus;
+use alloy::primitives::{Address, B256, U256};
 use anyhow::Result;
-use chrono::{NaiveDate, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use std::str::FromStr;
-use std::sync::Arc;
-use std::time::Duration;
+use tracing::{info, warn};
 
 // Trait for getting campaigns (abstraction layer)
 #[async_trait::async_trait]
 pub trait CampaignConfigSource: Send + Sync {
     async fn get_campaigns(&self) -> Result<Vec<CampaignConfig>>;
+
+    // Version of the campaign set last returned, recorded in run reports
+    fn version(&self) -> Option<String> {
+        None
+    }
 }
 pub struct BoostRewardsJob {
     config: ChainConfig,
@@ -25,6 +36,16 @@ pub struct BoostRewardsJob {
     duration_days: u64, // Calculated from start_date and end_date
     campaign_id: Option<String>,
     dry_run: bool,
+    report: RunRecorder,
+}
+
+// What a single campaign run sent today; tx hashes are None for dry runs
+#[derive(Debug, Clone)]
+pub struct BoostDistribution {
+    pub token_symbol: String,
+    pub daily_amount_wei: U256,
+    pub transfer_tx: Option<B256>,
+    pub boost_reward_tx: Option<B256>,
 }
 
 #[derive(Debug, Clone)]
@@ -92,6 +113,10 @@ impl BoostRewardsJob {
             ));
         }
 
+        let label = campaign_id.as_deref().unwrap_or("manual");
+        enforce_token_limits(&config, label, token_addr, total_amount, duration_days)?;
+
+        let report = RunRecorder::detached(&config);
         Ok(Self {
             config,
             token_address: token_addr,
@@ -101,13 +126,28 @@ impl BoostRewardsJob {
             duration_days,
             campaign_id,
             dry_run,
+            report,
         })
     }
 
-    pub async fn execute(&self) -> Result<()> {
-        println!("🚀 Boost Rewards Distribution Starting...");
+    pub fn with_report(mut self, report: RunRecorder) -> Self {
+        self.report = report;
+        self
+    }
+
+    // Campaigns share one report, so their reads are keyed by campaign
+    fn campaign_label(&self) -> &str {
+        self.campaign_id.as_deref().unwrap_or("manual")
+    }
+
+    fn read_key(&self, name: &str) -> String {
+        format!("{}.{}", self.campaign_label(), name)
+    }
+
+    pub async fn execute(&self, ctx: &JobContext) -> Result<BoostDistribution> {
+        info!("🚀 Boost Rewards Distribution Starting...");
         if let Some(id) = &self.campaign_id {
-            println!("   Campaign ID: {}", id);
+            info!("   Campaign ID: {}", id);
         }
 
         // 0. Validate date range first (early return)
@@ -127,50 +167,41 @@ impl BoostRewardsJob {
             ));
         }
 
-        // 1. Setup retry config and initialize client
-        let retry_config = RetryConfig::new(
-            self.config.retry.max_attempts,
-            Duration::from_secs(self.config.retry.base_delay_seconds),
-            Duration::from_secs(self.config.retry.max_delay_seconds),
-            self.config.retry.backoff_multiplier,
-        );
-
-        let kms_config = self
-            .config
-            .kms
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("KMS configuration is required"))?;
-
-        println!("🔐 Using KMS signing with key: {}", kms_config.key_id);
-        let client = execute_with_retry(
-            || {
-                let rpc_url = self.config.chain.rpc_url.clone();
-                let chain_id = self.config.chain.chain_id;
-                let key_id = kms_config.key_id.clone();
-                async move {
-                    BlockchainClient::new(&rpc_url, chain_id, &key_id, &self.config).await
-                }
-            },
-            &retry_config,
-            "Blockchain connection (KMS)",
-        ).await?;
-
-        // Create Arc once to avoid cloning
-        let client_arc = Arc::new(client);
+        // 1. Shared client, retry config and monitor come from the context
+        let client = &ctx.client_for("boost-rewards");
+        let retry_config = ctx.retry_config();
 
         // 2. Validate token contract and get decimals
-        println!("🔍 Validating token contract...");
-        let token_contract = ERC20Contract::new(self.token_address, client_arc.provider());
-
-        let keeper_address = client_arc.keeper_address();
-        // Get token details and keeper balance
-        let (token_decimals, token_symbol, keeper_balance) = tokio::try_join!(
-            token_contract.decimals(),
-            token_contract.symbol(),
-            token_contract.balance_of(keeper_address),
-        )?;
+        info!("🔍 Validating token contract...");
+        let token_contract =
+            ERC20Contract::new(self.token_address, client.provider(), client.clone());
+
+        let keeper_address = client.keeper_address();
+        // Get token details and keeper balance, batched at one block when Multicall3 is there.
+        // Only a missing Multicall3 falls back; other failures fail the job.
+        let (token_decimals, token_symbol, keeper_balance) =
+            match token_contract.token_state(keeper_address, None).await {
+                Ok(state) => {
+                    info!("   Token state read at block {}", state.block_number);
+                    (state.decimals, state.symbol, state.balance)
+                }
+                Err(e) if e.downcast_ref::<MulticallUnavailable>().is_some() => {
+                    warn!("⚠️  {}, reading one by one", e);
+                    tokio::try_join!(
+                        token_contract.decimals(),
+                        token_contract.symbol(),
+                        token_contract.balance_of(keeper_address),
+                    )?
+                }
+                Err(e) => return Err(e),
+            };
 
-        println!("   Token: {} ({} decimals)", token_symbol, token_decimals);
+        info!("   Token: {} ({} decimals)", token_symbol, token_decimals);
+        self.report.keeper_address(keeper_address);
+        self.report
+            .read(&self.read_key("token"), format!("{:?}", self.token_address));
+        self.report
+            .read(&self.read_key("keeper_balance"), keeper_balance.to_string());
 
         // 3. Calculate daily amount with overflow checks
         let multiplier = 10_f64.powi(token_decimals as i32);
@@ -227,10 +258,10 @@ impl BoostRewardsJob {
 
         let daily_amount_human = self.total_amount / self.duration_days as f64;
 
-        println!("💰 Campaign Details:");
-        println!("   Total Amount: {} {}", self.total_amount, token_symbol);
-        println!("   Duration: {} days", self.duration_days);
-        println!(
+        info!("💰 Campaign Details:");
+        info!("   Total Amount: {} {}", self.total_amount, token_symbol);
+        info!("   Duration: {} days", self.duration_days);
+        info!(
             "   Daily Amount: {:.2} {}",
             daily_amount_human, token_symbol
         );
@@ -239,20 +270,23 @@ impl BoostRewardsJob {
         // Note: These are already validated to be non-negative by date range checks above
         let days_elapsed = (today - self.start_date).num_days().max(0);
         let days_remaining = (self.end_date - today).num_days().max(0);
-        println!("📅 Date Validation:");
-        println!("   Start Date: {}", self.start_date);
-        println!("   End Date: {}", self.end_date);
-        println!("   Today: {}", today);
-        println!("   Days Elapsed: {}", days_elapsed);
-        println!("   Days Remaining: {}", days_remaining);
+        info!("📅 Date Validation:");
+        info!("   Start Date: {}", self.start_date);
+        info!("   End Date: {}", self.end_date);
+        info!("   Today: {}", today);
+        info!("   Days Elapsed: {}", days_elapsed);
+        info!("   Days Remaining: {}", days_remaining);
+
+        self.report
+            .read(&self.read_key("daily_amount"), daily_amount_wei.to_string());
 
         // 5. Check keeper balance
         if keeper_balance < daily_amount_wei {
-            return Err(anyhow::anyhow!(
-                "Insufficient token balance for today: keeper has {}, need {}",
-                keeper_balance,
-                daily_amount_wei
-            ));
+            return Err(KeeperError::InsufficientFunds(format!(
+                "keeper has {} {}, need {} for today",
+                keeper_balance, token_symbol, daily_amount_wei
+            ))
+            .into());
         }
 
         // Check remaining campaign amount (warning)
@@ -268,31 +302,52 @@ impl BoostRewardsJob {
             keeper_balance.to_string().parse::<f64>()? / 10_f64.powi(token_decimals as i32);
         let remaining_amount_human =
             remaining_amount_wei.to_string().parse::<f64>()? / 10_f64.powi(token_decimals as i32);
-
-        println!("💵 Balance Check:");
-        println!(
+        let chain_label = self.config.chain.chain_id.to_string();
+        metrics()
+            .token_balance
+            .with_label_values(&[&chain_label, &token_symbol])
+            .set(keeper_balance_human);
+
+        info!("💵 Balance Check:");
+        info!(
             "   Keeper Balance: {:.2} {}",
             keeper_balance_human, token_symbol
         );
-        println!(
+        info!(
             "   Daily Amount Required: {:.2} {}",
             daily_amount_human, token_symbol
         );
-        println!(
+        info!(
             "   Remaining Campaign Amount Required: {:.2} {} ({} days remaining)",
             remaining_amount_human, token_symbol, days_for_remaining_calc
         );
 
         if keeper_balance < remaining_amount_wei {
-            println!(
+            warn!(
                 "   ⚠️  WARNING: Keeper balance ({:.2} {}) is less than remaining campaign amount ({:.2} {}).",
                 keeper_balance_human, token_symbol, remaining_amount_human, token_symbol
             );
-            println!(
+            warn!(
                 "   ⚠️  Campaign will proceed, but may fail on future days if balance is not replenished."
             );
+            ctx.notifier()
+                .notify(Alert::new(
+                    AlertKind::LowTokenBalance,
+                    self.config.chain.chain_id,
+                    format!("{:?}", self.token_address),
+                    format!(
+                        "Keeper balance {:.2} {} is below the {:.2} {} still needed for campaign {} ({} days remaining)",
+                        keeper_balance_human,
+                        token_symbol,
+                        remaining_amount_human,
+                        token_symbol,
+                        self.campaign_label(),
+                        days_for_remaining_calc
+                    ),
+                ))
+                .await;
         } else {
-            println!("   ✅ Sufficient balance for remaining campaign duration");
+            info!("   ✅ Sufficient balance for remaining campaign duration");
         }
 
         // 6. Get earn vault address
@@ -305,102 +360,237 @@ impl BoostRewardsJob {
         let earn_vault_addr = Address::from_str(earn_vault_address)?;
 
         if self.dry_run {
-            println!(
+            info!(
                 "✅ DRY RUN: Would transfer {} {} to Earn Vault",
                 daily_amount_human, token_symbol
             );
-            println!(
+            info!(
                 "✅ DRY RUN: Would call onBoostReward({}, {})",
                 self.token_address, daily_amount_wei
             );
-            return Ok(());
+            self.report.decision(format!(
+                "dry run: would pay {} {} for campaign {}",
+                daily_amount_wei,
+                token_symbol,
+                self.campaign_label()
+            ));
+            return Ok(BoostDistribution {
+                token_symbol,
+                daily_amount_wei,
+                transfer_tx: None,
+                boost_reward_tx: None,
+            });
         }
 
-        // 7. Transfer tokens to Earn Vault
-        println!("📤 Transferring tokens to Earn Vault...");
-        let transfer_tx = execute_with_retry(
+        // 7. Transfer tokens to Earn Vault, unless a run that died before onBoostReward
+        // already did so today
+        let transfer_tx = match self.journaled_transfer(ctx, earn_vault_addr, daily_amount_wei)? {
+            Some(tx_hash) => {
+                info!(
+                    "♻️  Transfer {:?} already confirmed today, resuming at onBoostReward",
+                    tx_hash
+                );
+                self.report.decision(format!(
+                    "resumed: transfer {:?} confirmed by a previous run",
+                    tx_hash
+                ));
+                tx_hash
+            }
+            None => {
+                self.enforce_daily_total(client, daily_amount_human).await?;
+                self.transfer_to_vault(ctx, &token_contract, earn_vault_addr, daily_amount_wei)
+                    .await?
+            }
+        };
+
+        info!("📞 Calling onBoostReward on Earn Vault...");
+        let earn_vault = EarnVaultContract::new(earn_vault_addr, client.clone());
+
+        let boost_reward_tx = execute_with_retry(
             || {
-                let contract = token_contract.clone();
+                let contract = earn_vault.clone();
+                let campaign = self.campaign_label();
+                let token = self.token_address;
                 let amount = daily_amount_wei;
-                let to = earn_vault_addr;
-                async move { contract.transfer(to, amount).await }
+                async move { contract.on_boost_reward(campaign, token, amount).await }
             },
-            &retry_config,
-            "Token transfer",
+            retry_config,
+            "onBoostReward call",
         )
         .await?;
 
-        println!("   Transfer TX: {:?}", transfer_tx);
-
-        // Monitor transfer transaction
-        let timeout_gas_used = U256::from_str(&self.config.monitoring.timeout_gas_used)?;
-        let monitor = TransactionMonitor::new_with_timeout_values(
-            client_arc.provider(),
-            Duration::from_secs(self.config.monitoring.transaction_timeout_seconds),
-            Duration::from_secs(self.config.monitoring.poll_interval_seconds),
-            self.config.monitoring.timeout_block_number,
-            timeout_gas_used,
-        );
+        info!("   onBoostReward TX: {:?}", boost_reward_tx);
 
-        let transfer_receipt = monitor.monitor_transaction(transfer_tx).await?;
-        match transfer_receipt.status {
+        // Monitor onBoostReward transaction
+        let boost_reward_receipt = ctx
+            .monitor_transaction("onBoostReward", boost_reward_tx)
+            .await?;
+        self.report
+            .transaction(&self.read_key("onBoostReward"), &boost_reward_receipt);
+        match boost_reward_receipt.status {
             TransactionStatus::Success => {
-                println!(
-                    "✅ Transfer confirmed in block {}",
-                    transfer_receipt.block_number
+                info!(
+                    "✅ onBoostReward confirmed in block {}",
+                    boost_reward_receipt.block_number
                 );
+                info!("🎉 Distribution completed successfully!");
+                info!("   Days Remaining: {}", days_remaining);
+                metrics()
+                    .campaign_paid
+                    .with_label_values(&[&chain_label, self.campaign_label(), &token_symbol])
+                    .inc_by(daily_amount_human);
+                self.report.decision(format!(
+                    "paid {} {} for campaign {}",
+                    daily_amount_wei,
+                    token_symbol,
+                    self.campaign_label()
+                ));
             }
             TransactionStatus::Failed => {
-                return Err(anyhow::anyhow!("Token transfer failed"));
+                return Err(KeeperError::TransactionReverted(
+                    "onBoostReward call failed - tokens already transferred".to_string(),
+                )
+                .into());
             }
             TransactionStatus::Timeout => {
-                return Err(anyhow::anyhow!("Token transfer monitoring timeout"));
+                return Err(KeeperError::TransactionTimeout(
+                    "onBoostReward monitoring timeout - tokens already transferred".to_string(),
+                )
+                .into());
             }
         }
 
-        println!("📞 Calling onBoostReward on Earn Vault...");
-        let earn_vault = EarnVaultContract::new(earn_vault_addr, client_arc.provider());
+        Ok(BoostDistribution {
+            token_symbol,
+            daily_amount_wei,
+            transfer_tx: Some(transfer_tx),
+            boost_reward_tx: Some(boost_reward_tx),
+        })
+    }
 
-        let boost_reward_tx = execute_with_retry(
+    async fn transfer_to_vault(
+        &self,
+        ctx: &JobContext,
+        token_contract: &ERC20Contract,
+        earn_vault_addr: Address,
+        daily_amount_wei: U256,
+    ) -> Result<B256> {
+        let retry_config = ctx.retry_config();
+        info!("📤 Transferring tokens to Earn Vault...");
+        let transfer_tx = execute_with_retry(
             || {
-                let contract = earn_vault.clone();
-                let token = self.token_address;
+                let contract = token_contract.clone();
+                let campaign = self.campaign_label();
                 let amount = daily_amount_wei;
-                async move { contract.on_boost_reward(token, amount).await }
+                let to = earn_vault_addr;
+                async move { contract.transfer(campaign, to, amount).await }
             },
-            &retry_config,
-            "onBoostReward call",
+            retry_config,
+            "Token transfer",
         )
         .await?;
 
-        println!("   onBoostReward TX: {:?}", boost_reward_tx);
+        info!("   Transfer TX: {:?}", transfer_tx);
 
-        // Monitor onBoostReward transaction
-        let boost_reward_receipt = monitor.monitor_transaction(boost_reward_tx).await?;
-        match boost_reward_receipt.status {
+        // Monitor transfer transaction
+
+        let transfer_receipt = ctx
+            .monitor_transaction("Boost token transfer", transfer_tx)
+            .await?;
+        self.report
+            .transaction(&self.read_key("transfer"), &transfer_receipt);
+        match transfer_receipt.status {
             TransactionStatus::Success => {
-                println!(
-                    "✅ onBoostReward confirmed in block {}",
-                    boost_reward_receipt.block_number
+                info!(
+                    "✅ Transfer confirmed in block {}",
+                    transfer_receipt.block_number
                 );
-                println!("🎉 Distribution completed successfully!");
-                println!("   Days Remaining: {}", days_remaining);
             }
             TransactionStatus::Failed => {
-                return Err(anyhow::anyhow!(
-                    "onBoostReward call failed - tokens already transferred"
-                ));
+                return Err(
+                    KeeperError::TransactionReverted("Token transfer failed".to_string()).into(),
+                );
             }
             TransactionStatus::Timeout => {
-                return Err(anyhow::anyhow!(
-                    "onBoostReward monitoring timeout - tokens already transferred"
-                ));
+                return Err(KeeperError::TransactionTimeout(
+                    "Token transfer monitoring timeout".to_string(),
+                )
+                .into());
             }
         }
 
+        Ok(transfer_tx)
+    }
+
+    // Hash of today's confirmed transfer for this payout if no onBoostReward followed it
+    // Campaign-level limits are checked up front; the day's total also counts what
+    // earlier runs (other campaigns, manual reruns) already sent
+    async fn enforce_daily_total(
+        &self,
+        client: &BlockchainClient,
+        daily_amount_human: f64,
+    ) -> Result<()> {
+        let Some(allowed_tokens) = &self.config.boost_tokens else {
+            return Ok(());
+        };
+        let Some(limit) = find_token_limit(allowed_tokens, self.token_address)? else {
+            return Ok(());
+        };
+
+        let (_, sent_today) = payouts_today(client, self.token_address).await?;
+        self.report.read(
+            "daily_total",
+            format!(
+                "{} already sent, {} planned",
+                sent_today, daily_amount_human
+            ),
+        );
+        if let Err(e) = check_daily_total(
+            limit,
+            self.token_address,
+            sent_today,
+            daily_amount_human,
+            &format!("campaign {}", self.campaign_label()),
+        ) {
+            self.report.decision(format!("refused: {}", e));
+            return Err(e);
+        }
         Ok(())
     }
 
+    fn journaled_transfer(
+        &self,
+        ctx: &JobContext,
+        earn_vault_addr: Address,
+        daily_amount_wei: U256,
+    ) -> Result<Option<B256>> {
+        let Some(journal) = ctx.client().journal() else {
+            return Ok(None);
+        };
+        let today = start_of_today();
+        let transfer_intent = ERC20Contract::transfer_intent(
+            self.campaign_label(),
+            self.token_address,
+            earn_vault_addr,
+            daily_amount_wei,
+        );
+        let Some(transfer) = journal.latest(&transfer_intent, today)? else {
+            return Ok(None);
+        };
+        if transfer.status != JournalStatus::Confirmed {
+            return Ok(None);
+        }
+        let boost_intent = EarnVaultContract::boost_reward_intent(
+            self.campaign_label(),
+            self.token_address,
+            daily_amount_wei,
+        );
+        let boosted = journal
+            .latest(&boost_intent, transfer.updated_at)?
+            .is_some_and(|entry| entry.status == JournalStatus::Confirmed);
+        Ok(if boosted { None } else { transfer.hash })
+    }
+
     pub fn from_campaign_config(
         config: ChainConfig,
         campaign: CampaignConfig,
@@ -424,6 +614,15 @@ impl BoostRewardsJob {
             ));
         }
 
+        enforce_token_limits(
+            &config,
+            &campaign.id,
+            campaign.token_address,
+            campaign.total_amount,
+            campaign.duration_days(),
+        )?;
+
+        let report = RunRecorder::detached(&config);
         Ok(Self {
             config,
             token_address: campaign.token_address,
@@ -433,6 +632,184 @@ impl BoostRewardsJob {
             duration_days: campaign.duration_days(),
             campaign_id: Some(campaign.id),
             dry_run,
+            report,
         })
     }
 }
+
+// Guardrail against compromised or fat-fingered campaign files: only allowlisted
+// tokens may leave the keeper wallet, and only up to the configured caps.
+// Without a [[boost_tokens]] section nothing is paid unless [boost] allow_any_token is set.
+fn enforce_token_limits(
+    config: &ChainConfig,
+    campaign_label: &str,
+    token_address: Address,
+    total_amount: f64,
+    duration_days: u64,
+) -> Result<()> {
+    let Some(allowed_tokens) = &config.boost_tokens else {
+        if !config.boost.allow_any_token {
+            return Err(anyhow::anyhow!(
+                "No boost_tokens allowlist configured, refusing campaign {}; set allow_any_token = true under [boost] to pay out without limits",
+                campaign_label
+            ));
+        }
+        warn!("⚠️  No boost_tokens allowlist configured, token limits are not enforced");
+        return Ok(());
+    };
+
+    let limit = find_token_limit(allowed_tokens, token_address)?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "Token {} for campaign {} is not in the boost_tokens allowlist",
+            token_address,
+            campaign_label
+        )
+    })?;
+
+    if total_amount > limit.max_campaign_amount {
+        return Err(anyhow::anyhow!(
+            "Campaign {} total amount {} exceeds max_campaign_amount {} for token {}",
+            campaign_label,
+            total_amount,
+            limit.max_campaign_amount,
+            token_address
+        ));
+    }
+
+    let daily_amount = total_amount / duration_days as f64;
+    if daily_amount > limit.max_daily_amount {
+        return Err(anyhow::anyhow!(
+            "Campaign {} daily amount {} exceeds max_daily_amount {} for token {}",
+            campaign_label,
+            daily_amount,
+            limit.max_daily_amount,
+            token_address
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn find_token_limit(
+    allowed_tokens: &[BoostTokenLimit],
+    token_address: Address,
+) -> Result<Option<&BoostTokenLimit>> {
+    for entry in allowed_tokens {
+        let address = Address::from_str(&entry.address).map_err(|e| {
+            anyhow::anyhow!("Invalid boost_tokens address {}: {}", entry.address, e)
+        })?;
+        if address == token_address {
+            return Ok(Some(entry));
+        }
+    }
+    Ok(None)
+}
+
+// A boost transfer found in the journal
+#[derive(Debug, Clone)]
+pub struct JournaledPayout {
+    pub campaign: Option<String>,
+    pub amount: U256,
+    // Confirmed, but no onBoostReward followed; the next run resumes it without a new transfer
+    pub awaiting_boost: bool,
+}
+
+// Transfers of `token` journaled since `since` that may have moved funds: everything
+// except the ones that reverted or never made it on chain
+pub fn journaled_payouts(
+    journal: &TxJournal,
+    token_address: Address,
+    since: DateTime<Utc>,
+) -> Result<Vec<JournaledPayout>> {
+    let mut payouts = Vec::new();
+    for entry in journal.entries()? {
+        if entry.updated_at < since
+            || matches!(
+                entry.status,
+                JournalStatus::Reverted | JournalStatus::Dropped
+            )
+        {
+            continue;
+        }
+        let Some((token, amount, campaign)) = ERC20Contract::parse_transfer_intent(&entry.intent)
+        else {
+            continue;
+        };
+        if token != token_address {
+            continue;
+        }
+
+        let awaiting_boost = match campaign {
+            Some(campaign) if entry.status == JournalStatus::Confirmed => {
+                let boost_intent = EarnVaultContract::boost_reward_intent(campaign, token, amount);
+                !journal
+                    .latest(&boost_intent, entry.updated_at)?
+                    .is_some_and(|boost| boost.status == JournalStatus::Confirmed)
+            }
+            _ => false,
+        };
+        payouts.push(JournaledPayout {
+            campaign: campaign.map(str::to_string),
+            amount,
+            awaiting_boost,
+        });
+    }
+    Ok(payouts)
+}
+
+// Today's journaled payouts of `token` and their sum in whole tokens. max_daily_amount
+// can't be enforced without the journal, so a client without one is refused
+pub async fn payouts_today(
+    client: &BlockchainClient,
+    token_address: Address,
+) -> Result<(Vec<JournaledPayout>, f64)> {
+    let journal = client.journal().ok_or_else(|| {
+        KeeperError::Config(format!(
+            "max_daily_amount for token {} needs the transaction journal to count earlier payouts",
+            token_address
+        ))
+    })?;
+    let payouts = journaled_payouts(journal, token_address, start_of_today())?;
+
+    let mut amount = 0.0;
+    if !payouts.is_empty() {
+        let decimals = ERC20Contract::new(token_address, client.provider(), client.clone())
+            .decimals()
+            .await?;
+        for payout in &payouts {
+            amount += payout.amount.to_string().parse::<f64>()? / 10_f64.powi(decimals as i32);
+        }
+    }
+    Ok((payouts, amount))
+}
+
+pub fn check_daily_total(
+    limit: &BoostTokenLimit,
+    token_address: Address,
+    sent_today: f64,
+    planned: f64,
+    planned_by: &str,
+) -> Result<()> {
+    let total = sent_today + planned;
+    if total > limit.max_daily_amount {
+        return Err(anyhow::anyhow!(
+            "Daily total {} for token {} ({} already sent today, {} planned by {}) exceeds max_daily_amount {}",
+            total,
+            token_address,
+            sent_today,
+            planned,
+            planned_by,
+            limit.max_daily_amount
+        ));
+    }
+    Ok(())
+}
+
+// Payouts are counted per UTC day
+pub fn start_of_today() -> DateTime<Utc> {
+    Utc::now()
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .map(|midnight| midnight.and_utc())
+        .unwrap_or_else(Utc::now)
+}
@@ -1,46 +1,78 @@
 use crate::config::ChainConfig;
-use crate::jobs::boost_rewards::{BoostRewardsJob, CampaignConfig, CampaignConfigSource};
+use crate::error::KeeperError;
+use crate::jobs::boost_rewards::{
+    check_daily_total, find_token_limit, payouts_today, BoostDistribution, BoostRewardsJob,
+    CampaignConfig, CampaignConfigSource,
+};
+use crate::jobs::{JobContext, JobOutcome};
+use crate::report::RunRecorder;
+use alloy::primitives::Address;
 use anyhow::Result;
 use chrono::{NaiveDate, Utc};
-use std::time::Duration;
+use futures::stream::{self, StreamExt};
+use std::collections::BTreeMap;
+use tracing::{error, info, info_span, Instrument};
 
 // CronJob that processes boost reward campaigns from S3
 // Designed to run once daily (e.g., `0 12 * * *` for 12:00 UTC daily)
 pub struct BoostRewardsS3 {
     config: ChainConfig,
     campaign_source: Box<dyn CampaignConfigSource>,
-    delay_between_campaigns: Duration,
+    max_concurrent_campaigns: usize,
+    report: RunRecorder,
+}
+
+// Per-campaign result of a run, printed as a summary at the end
+#[derive(Debug)]
+pub struct CampaignSummary {
+    pub campaign_id: String,
+    pub token_address: Address,
+    pub result: Result<BoostDistribution, String>,
 }
 
 impl BoostRewardsS3 {
     pub fn new(config: ChainConfig, campaign_source: Box<dyn CampaignConfigSource>) -> Self {
+        let max_concurrent_campaigns = config.boost.max_concurrent_campaigns.max(1);
+        let report = RunRecorder::detached(&config);
         Self {
             config,
             campaign_source,
-            delay_between_campaigns: Duration::from_secs(30), // Default: 30 seconds between campaigns
+            max_concurrent_campaigns,
+            report,
         }
     }
 
-    pub async fn run(&self) -> Result<()> {
+    pub fn with_report(mut self, report: RunRecorder) -> Self {
+        self.report = report;
+        self
+    }
+
+    pub async fn run(&self, ctx: &JobContext) -> Result<Vec<CampaignSummary>> {
         let today = Utc::now().date_naive();
 
-        println!("🚀 Boost Rewards Service Starting (Daily CronJob)...");
+        info!("🚀 Boost Rewards Service Starting (Daily CronJob)...");
 
         // Scan S3 for campaigns
-        println!("📡 Scanning S3 for campaigns...");
+        info!("📡 Scanning S3 for campaigns...");
         let all_campaigns = self.campaign_source.get_campaigns().await?;
-        println!("   Found {} total campaigns in S3", all_campaigns.len());
+        info!("   Found {} total campaigns in S3", all_campaigns.len());
+        self.report
+            .campaigns_version(self.campaign_source.version());
+        self.report.read("campaigns_total", all_campaigns.len());
 
         // Process campaigns for today
-        let execution_result = self.process_campaigns_for_today(today, all_campaigns).await;
+        let execution_result = self
+            .process_campaigns_for_today(ctx, today, all_campaigns)
+            .await;
 
         // Handle execution result
         match &execution_result {
-            Ok(_) => {
-                println!("✅ Campaigns processed successfully for {}", today);
+            Ok(summaries) => {
+                info!("✅ Campaigns processed for {}", today);
+                Self::print_summary(summaries);
             }
             Err(e) => {
-                eprintln!("❌ Error processing campaigns: {}", e);
+                error!("❌ Error processing campaigns: {}", e);
             }
         }
 
@@ -50,18 +82,19 @@ impl BoostRewardsS3 {
         } else {
             "failure"
         };
-        println!("📊 Execution status for {}: {}", today, status);
+        info!("📊 Execution status for {}: {}", today, status);
 
         execution_result
     }
 
     async fn process_campaigns_for_today(
         &self,
+        ctx: &JobContext,
         today: NaiveDate,
         all_campaigns: Vec<CampaignConfig>,
-    ) -> Result<()> {
-        println!("📅 Processing campaigns for date: {}", today);
-        println!("   Found {} total campaigns", all_campaigns.len());
+    ) -> Result<Vec<CampaignSummary>> {
+        info!("📅 Processing campaigns for date: {}", today);
+        info!("   Found {} total campaigns", all_campaigns.len());
 
         // Filter and collect active campaigns for today
         let mut active_campaigns: Vec<_> = all_campaigns
@@ -69,52 +102,197 @@ impl BoostRewardsS3 {
             .filter(|x| x.is_active_for_date(today))
             .collect();
 
-        println!(
+        info!(
             "   Found {} active campaigns for today",
             active_campaigns.len()
         );
 
+        self.report.read(
+            "campaigns_active",
+            active_campaigns
+                .iter()
+                .map(|c| c.id.clone())
+                .collect::<Vec<_>>(),
+        );
+
         if active_campaigns.is_empty() {
-            println!("   No active campaigns, skipping...");
-            return Ok(());
+            info!("   No active campaigns, skipping...");
+            self.report.decision("skipped: no active campaigns");
+            return Ok(Vec::new());
         }
 
         // Sort campaigns by start date (earliest first)
         active_campaigns.sort_by_key(|x| x.start_date);
 
-        // Process each campaign sequentially
-        for (index, campaign) in active_campaigns.iter().enumerate() {
-            // Add delay before processing (except for the first campaign)
-            if index > 0 {
-                println!(
-                    "   ⏸️  Waiting {} seconds before next campaign...",
-                    self.delay_between_campaigns.as_secs()
+        self.enforce_daily_token_caps(ctx, &active_campaigns)
+            .await?;
+
+        info!(
+            "   Processing up to {} campaigns concurrently",
+            self.max_concurrent_campaigns
+        );
+
+        let total = active_campaigns.len();
+        let summaries = stream::iter(active_campaigns.into_iter().enumerate())
+            .map(|(index, campaign)| {
+                let span = info_span!(
+                    "campaign",
+                    campaign_id = %campaign.id,
+                    token = %campaign.token_address
                 );
-                tokio::time::sleep(self.delay_between_campaigns).await;
-            }
+                // All campaigns share the context's client, whose nonce allocator keeps
+                // concurrent sends apart
+                async move {
+                    info!(
+                        "🎯 Processing campaign: {} ({}/{})",
+                        campaign.id,
+                        index + 1,
+                        total
+                    );
+                    let result = self
+                        .process_single_campaign(ctx, &campaign)
+                        .await
+                        .map_err(|e| e.to_string());
+                    match &result {
+                        Ok(_) => info!("   ✅ Campaign {} completed successfully", campaign.id),
+                        Err(e) => {
+                            error!("   ❌ Campaign {} failed: {}", campaign.id, e);
+                            self.report
+                                .decision(format!("campaign {} failed: {}", campaign.id, e));
+                        }
+                    }
+                    CampaignSummary {
+                        campaign_id: campaign.id,
+                        token_address: campaign.token_address,
+                        result,
+                    }
+                }
+                .instrument(span)
+            })
+            .buffered(self.max_concurrent_campaigns)
+            .collect::<Vec<_>>()
+            .await;
+
+        Ok(summaries)
+    }
+
+    // max_daily_amount caps a token's total for the day, not each campaign: today's
+    // payouts of every active campaign plus what earlier runs already sent today
+    async fn enforce_daily_token_caps(
+        &self,
+        ctx: &JobContext,
+        campaigns: &[CampaignConfig],
+    ) -> Result<()> {
+        // No allowlist means allow_any_token opted out of limits
+        let Some(allowed_tokens) = &self.config.boost_tokens else {
+            return Ok(());
+        };
 
-            println!(
-                "🎯 Processing campaign: {} ({}/{})",
-                campaign.id,
-                index + 1,
-                active_campaigns.len()
+        let mut by_token: BTreeMap<Address, Vec<&CampaignConfig>> = BTreeMap::new();
+        for campaign in campaigns {
+            by_token
+                .entry(campaign.token_address)
+                .or_default()
+                .push(campaign);
+        }
+
+        let client = ctx.client_for("boost-rewards");
+        for (token, campaigns) in by_token {
+            // Tokens missing from the allowlist are rejected per campaign
+            let Some(limit) = find_token_limit(allowed_tokens, token)? else {
+                continue;
+            };
+
+            let (journaled, journaled_amount) = payouts_today(&client, token).await?;
+
+            // A campaign resuming at onBoostReward doesn't transfer again
+            let planned_amount: f64 = campaigns
+                .iter()
+                .filter(|campaign| {
+                    !journaled.iter().any(|payout| {
+                        payout.awaiting_boost
+                            && payout.campaign.as_deref() == Some(campaign.id.as_str())
+                    })
+                })
+                .map(|campaign| campaign.total_amount / campaign.duration_days() as f64)
+                .sum();
+
+            self.report.read(
+                &format!("daily_total.{:?}", token),
+                format!(
+                    "{} already sent, {} planned",
+                    journaled_amount, planned_amount
+                ),
             );
-            match self.process_single_campaign(campaign).await {
-                Ok(_) => println!("   ✅ Campaign {} completed successfully", campaign.id),
-                Err(e) => {
-                    eprintln!("   ❌ Campaign {} failed: {}", campaign.id, e);
-                    // Continue with next campaign
-                }
+            if let Err(e) = check_daily_total(
+                limit,
+                token,
+                journaled_amount,
+                planned_amount,
+                &format!("{} campaigns", campaigns.len()),
+            ) {
+                self.report.decision(format!("refused: {}", e));
+                return Err(e);
             }
         }
-
         Ok(())
     }
 
-    async fn process_single_campaign(&self, campaign: &CampaignConfig) -> Result<()> {
+    async fn process_single_campaign(
+        &self,
+        ctx: &JobContext,
+        campaign: &CampaignConfig,
+    ) -> Result<BoostDistribution> {
         let job =
-            BoostRewardsJob::from_campaign_config(self.config.clone(), campaign.clone(), false)?;
+            BoostRewardsJob::from_campaign_config(self.config.clone(), campaign.clone(), false)?
+                .with_report(self.report.clone());
+
+        job.execute(ctx).await
+    }
 
-        job.execute().await
+    // A run with no active campaigns is a no-op; any failed campaign fails the run
+    pub fn outcome(summaries: &[CampaignSummary]) -> Result<JobOutcome> {
+        if summaries.is_empty() {
+            return Ok(JobOutcome::NoOp);
+        }
+        let failed = summaries.iter().filter(|s| s.result.is_err()).count();
+        if failed > 0 {
+            return Err(KeeperError::CampaignsFailed {
+                failed,
+                total: summaries.len(),
+            }
+            .into());
+        }
+        Ok(JobOutcome::Completed)
+    }
+
+    fn print_summary(summaries: &[CampaignSummary]) {
+        if summaries.is_empty() {
+            return;
+        }
+
+        let succeeded = summaries.iter().filter(|s| s.result.is_ok()).count();
+        info!(
+            "📋 Campaign summary ({}/{} succeeded):",
+            succeeded,
+            summaries.len()
+        );
+        for summary in summaries {
+            match &summary.result {
+                Ok(distribution) => info!(
+                    "   ✅ {} [{}] sent {} {} (transfer: {:?}, onBoostReward: {:?})",
+                    summary.campaign_id,
+                    summary.token_address,
+                    distribution.daily_amount_wei,
+                    distribution.token_symbol,
+                    distribution.transfer_tx,
+                    distribution.boost_reward_tx
+                ),
+                Err(e) => error!(
+                    "   ❌ {} [{}] failed: {}",
+                    summary.campaign_id, summary.token_address, e
+                ),
+            }
+        }
     }
 }
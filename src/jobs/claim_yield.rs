@@ -1,50 +1,42 @@
-use crate::blockchain::BlockchainClient;
 use crate::config::ChainConfig;
 use crate::contracts::usdsc::USDSCContract;
-use crate::retry::{execute_with_retry, RetryConfig};
-use crate::transaction_monitor::{TransactionMonitor, TransactionStatus};
+use crate::error::KeeperError;
+use crate::jobs::{JobContext, JobOutcome};
+use crate::metrics::metrics;
+use crate::report::RunRecorder;
+use crate::retry::execute_with_retry;
+use crate::transaction_monitor::TransactionStatus;
 use alloy::primitives::{Address, U256};
 use anyhow::Result;
 use std::str::FromStr;
-use std::time::Duration;
+use tracing::{error, info};
 
 pub struct ClaimYieldJob {
     config: ChainConfig,
     dry_run: bool,
+    report: RunRecorder,
 }
 
 impl ClaimYieldJob {
     pub fn new(config: ChainConfig, dry_run: bool) -> Self {
-        Self { config, dry_run }
+        let report = RunRecorder::detached(&config);
+        Self {
+            config,
+            dry_run,
+            report,
+        }
     }
 
-    pub async fn execute(&self) -> Result<()> {
-        println!("🔍 ClaimYield Job Starting...");
-
-        let retry_config = RetryConfig::new(
-            self.config.retry.max_attempts,
-            Duration::from_secs(self.config.retry.base_delay_seconds),
-            Duration::from_secs(self.config.retry.max_delay_seconds),
-            self.config.retry.backoff_multiplier,
-        );
+    pub fn with_report(mut self, report: RunRecorder) -> Self {
+        self.report = report;
+        self
+    }
 
-        // KMS signing is required
-        let kms_config = self.config.kms.as_ref()
-            .ok_or_else(|| anyhow::anyhow!("KMS configuration is required. Please configure KMS settings in your config file or via CLI."))?;
+    pub async fn execute(&self, ctx: &JobContext) -> Result<JobOutcome> {
+        info!("🔍 ClaimYield Job Starting...");
 
-        println!("🔐 Using KMS signing with key: {}", kms_config.key_id);
-        let client = execute_with_retry(
-            || {
-                let rpc_url = self.config.chain.rpc_url.clone();
-                let chain_id = self.config.chain.chain_id;
-                let key_id = kms_config.key_id.clone();
-                async move {
-                    BlockchainClient::new(&rpc_url, chain_id, &key_id, &self.config).await
-                }
-            },
-            &retry_config,
-            "Blockchain connection (KMS)",
-        ).await?;
+        let client = &ctx.client_for("claim-yield");
+        let retry_config = ctx.retry_config();
 
         let usdsc_contract = USDSCContract::new(
             Address::from_str(&self.config.contracts.usdsc_address)?,
@@ -53,19 +45,41 @@ impl ClaimYieldJob {
         );
 
         let pending_yield = usdsc_contract.get_pending_yield().await?;
-        println!("💰 Pending yield: {}", pending_yield);
+        info!("💰 Pending yield: {}", pending_yield);
+        metrics().record_pending_yield(self.config.chain.chain_id, pending_yield);
 
         let min_threshold = U256::from_str(&self.config.thresholds.min_yield_threshold)?;
+        self.report.keeper_address(client.keeper_address());
+        self.report.read("pending_yield", pending_yield.to_string());
+        self.report
+            .read("min_yield_threshold", min_threshold.to_string());
 
         if pending_yield >= min_threshold {
-            println!(
+            info!(
                 "💰 Yield above threshold ({} >= {}), claiming...",
                 pending_yield, min_threshold
             );
 
+            let claim_tx =
+                usdsc_contract.claim_yield_request(&self.config.transaction.value_wei)?;
+            if ctx
+                .below_profitability(pending_yield, vec![claim_tx], &self.report)
+                .await?
+            {
+                return Ok(JobOutcome::NoOp);
+            }
+
+            if ctx
+                .defer_for_gas("claim-yield", self.dry_run, &self.report)
+                .await?
+            {
+                return Ok(JobOutcome::Deferred);
+            }
+
             if self.dry_run {
-                println!("✅ DRY RUN: Would claim yield transaction");
-                return Ok(());
+                info!("✅ DRY RUN: Would claim yield transaction");
+                self.report.decision("dry run: would claim yield");
+                return Ok(JobOutcome::Completed);
             }
 
             let tx_hash = execute_with_retry(
@@ -74,46 +88,46 @@ impl ClaimYieldJob {
                     let value_wei = self.config.transaction.value_wei.clone();
                     async move { contract.claim_yield(&value_wei).await }
                 },
-                &retry_config,
+                retry_config,
                 "Claim yield transaction",
             )
             .await?;
-            println!("✅ Claim transaction sent: {:?}", tx_hash);
+            info!("✅ Claim transaction sent: {:?}", tx_hash);
 
-            let timeout_gas_used = U256::from_str(&self.config.monitoring.timeout_gas_used)?;
-            let monitor = TransactionMonitor::new_with_timeout_values(
-                client.provider(),
-                Duration::from_secs(self.config.monitoring.transaction_timeout_seconds),
-                Duration::from_secs(self.config.monitoring.poll_interval_seconds),
-                self.config.monitoring.timeout_block_number,
-                timeout_gas_used,
-            );
-
-            let receipt = monitor.monitor_transaction(tx_hash).await?;
+            let receipt = ctx.monitor_transaction("Claim yield", tx_hash).await?;
+            self.report.transaction("claimYield", &receipt);
             match receipt.status {
                 TransactionStatus::Success => {
-                    println!(
+                    info!(
                         "🎉 Claim transaction confirmed in block {}",
                         receipt.block_number
                     );
-                    println!("⛽ Gas used: {}", receipt.gas_used);
+                    info!("⛽ Gas used: {}", receipt.gas_used);
+                    self.report.decision("claimed yield");
                 }
                 TransactionStatus::Failed => {
-                    println!("❌ Claim transaction failed");
-                    return Err(anyhow::anyhow!("Transaction failed"));
+                    error!("❌ Claim transaction failed");
+                    return Err(
+                        KeeperError::TransactionReverted("Transaction failed".to_string()).into(),
+                    );
                 }
                 TransactionStatus::Timeout => {
-                    println!("⏰ Claim transaction monitoring timeout");
-                    return Err(anyhow::anyhow!("Transaction monitoring timeout"));
+                    info!("⏰ Claim transaction monitoring timeout");
+                    return Err(KeeperError::TransactionTimeout(
+                        "Transaction monitoring timeout".to_string(),
+                    )
+                    .into());
                 }
             }
         } else {
-            println!(
+            info!(
                 "⏳ Yield below threshold ({} < {}), skipping claim",
                 pending_yield, min_threshold
             );
+            self.report.decision("skipped: yield below threshold");
+            return Ok(JobOutcome::NoOp);
         }
 
-        Ok(())
+        Ok(JobOutcome::Completed)
     }
 }
@@ -0,0 +1,534 @@
+use crate::block_waiter::BlockWaiter;
+use crate::blockchain::BlockchainClient;
+use crate::config::{ChainConfig, GasCeilingSettings};
+use crate::error::KeeperError;
+use crate::fees::{wei_to_gwei, FeeStrategy};
+use crate::gas_ceiling::{decide, Deferral, DeferralStore, GasDecision};
+use crate::journal::{JournalStatus, TxJournal};
+use crate::kms_signer::KmsSigner;
+use crate::metrics::metrics;
+use crate::notifier::{Alert, AlertKind, Notifier};
+use crate::private_relay::PrivateRelay;
+use crate::profitability::ProfitabilityGate;
+use crate::quorum::QuorumReader;
+use crate::report::RunRecorder;
+use crate::retry::{execute_with_retry, RetryConfig};
+use crate::rpc_pool::RpcPool;
+use crate::subscription_provider::SubscriptionProvider;
+use crate::transaction_monitor::{TransactionMonitor, TransactionReceipt, TransactionStatus};
+use alloy::primitives::utils::{format_ether, parse_ether};
+use alloy::primitives::{B256, U256};
+use alloy::rpc::types::TransactionRequest;
+use anyhow::Result;
+use chrono::Utc;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use tracing::{info, warn};
+
+// Everything a job needs to talk to the chain, built once per chain config and
+// borrowed by every job run in the process
+pub struct JobContext {
+    chain_id: u64,
+    client: BlockchainClient,
+    signer: Option<KmsSigner>,
+    retry_config: RetryConfig,
+    monitor: TransactionMonitor,
+    notifier: Arc<Notifier>,
+    max_block_age_seconds: u64,
+    block_waiter: BlockWaiter,
+    gas_ceilings: BTreeMap<String, GasCeilingSettings>,
+    deferrals: DeferralStore,
+    profitability: Option<ProfitabilityGate>,
+    private_relay: Option<Arc<PrivateRelay>>,
+    // Set once the journal left by earlier processes is settled
+    reconciled: OnceCell<()>,
+}
+
+impl JobContext {
+    pub async fn connect(config: &ChainConfig) -> Result<Self> {
+        let retry_config = Self::retry_config_from(config);
+
+        // KMS signing is required
+        let kms_config = config.kms.as_ref()
+            .ok_or_else(|| KeeperError::Config("KMS configuration is required. Please configure KMS settings in your config file or via CLI.".to_string()))?;
+        let aws_region = kms_config.region.clone()
+            .ok_or_else(|| KeeperError::Config("KMS region not configured. Set AWS_REGION environment variable or configure region in config file".to_string()))?;
+
+        info!("🔐 Using KMS signing with key: {}", kms_config.key_id);
+        let signer = execute_with_retry(
+            || {
+                let key_id = kms_config.key_id.clone();
+                let region = aws_region.clone();
+                async move { KmsSigner::new(key_id, region, config.chain.chain_id).await }
+            },
+            &retry_config,
+            "KMS signer initialization",
+        )
+        .await?;
+
+        let pool = RpcPool::new(&config.chain)?;
+        if pool.len() > 1 {
+            pool.check_health().await;
+            pool.spawn_health_checks();
+        }
+        let client =
+            execute_with_retry(
+                || {
+                    let signer = &signer;
+                    let pool = &pool;
+                    async move {
+                        BlockchainClient::with_signer(pool, config.chain.chain_id, signer).await
+                    }
+                },
+                &retry_config,
+                "Blockchain connection (KMS)",
+            )
+            .await?;
+
+        let journal = Arc::new(TxJournal::from_config(config)?);
+        info!("📒 Transaction journal: {}", journal.path().display());
+        let mut client = client
+            .with_journal(journal)
+            .with_fee_strategy(FeeStrategy::from_config(config));
+        if config.chain.op_stack {
+            client = client.with_l1_fee_oracle();
+        }
+        if let Some(subscriptions) = SubscriptionProvider::from_settings(&config.chain)? {
+            client = client.with_subscription_provider(subscriptions);
+        }
+        if let Some(quorum) = QuorumReader::from_config(config)? {
+            client = client.with_quorum(Arc::new(quorum));
+        }
+        let mut context = Self::from_client(config, client)?;
+        context.signer = Some(signer);
+        Ok(context)
+    }
+
+    // Builds a context around an existing client, e.g. one backed by a mocked provider
+    pub fn from_client(config: &ChainConfig, client: BlockchainClient) -> Result<Self> {
+        let timeout_gas_used = U256::from_str(&config.monitoring.timeout_gas_used)?;
+        let monitor = TransactionMonitor::new_with_timeout_values(
+            client.provider(),
+            Duration::from_secs(config.monitoring.transaction_timeout_seconds),
+            Duration::from_secs(config.monitoring.poll_interval_seconds),
+            config.monitoring.timeout_block_number,
+            timeout_gas_used,
+        )
+        .with_subscriptions(client.subscription_provider().cloned());
+
+        Ok(Self {
+            chain_id: config.chain.chain_id,
+            client,
+            signer: None,
+            retry_config: Self::retry_config_from(config),
+            monitor,
+            notifier: Arc::new(Notifier::new(config.alerts.clone())),
+            max_block_age_seconds: config.chain.max_block_age_seconds,
+            block_waiter: BlockWaiter::from_settings(&config.chain),
+            gas_ceilings: config.gas_ceiling.clone(),
+            deferrals: DeferralStore::from_config(config),
+            profitability: ProfitabilityGate::from_config(config)?,
+            private_relay: PrivateRelay::from_config(config)?.map(Arc::new),
+            reconciled: OnceCell::new(),
+        })
+    }
+
+    // Lets several contexts (or a context and its runner) share dedup and rate limits
+    pub fn with_notifier(mut self, notifier: Arc<Notifier>) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    fn retry_config_from(config: &ChainConfig) -> RetryConfig {
+        RetryConfig::new(
+            config.retry.max_attempts,
+            Duration::from_secs(config.retry.base_delay_seconds),
+            Duration::from_secs(config.retry.max_delay_seconds),
+            config.retry.backoff_multiplier,
+        )
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    pub fn client(&self) -> &BlockchainClient {
+        &self.client
+    }
+
+    // The shared client, sending through the private relay when it is enabled for `job`.
+    // Sends are journaled with `job`, so a reconcile rebroadcasts them the same way.
+    pub fn client_for(&self, job: &str) -> BlockchainClient {
+        let client = self.client.clone().with_job(job);
+        match &self.private_relay {
+            Some(relay) if relay.enabled_for(job) => client.with_private_relay(relay.clone()),
+            _ => client,
+        }
+    }
+
+    pub fn signer(&self) -> Option<&KmsSigner> {
+        self.signer.as_ref()
+    }
+
+    pub fn retry_config(&self) -> &RetryConfig {
+        &self.retry_config
+    }
+
+    #[allow(dead_code)] // Public API
+    pub fn monitor(&self) -> &TransactionMonitor {
+        &self.monitor
+    }
+
+    pub fn notifier(&self) -> &Notifier {
+        &self.notifier
+    }
+
+    // Returns the first block after the current one
+    pub async fn wait_for_next_block(&self) -> Result<u64> {
+        let initial_block = self.client.get_block_number().await?;
+        info!("⏳ Waiting for next block (current: {})...", initial_block);
+        let block = self
+            .block_waiter
+            .wait_for_block_after(&self.client, initial_block)
+            .await?;
+        info!("✅ New block confirmed: {}", block);
+        Ok(block)
+    }
+
+    // Waits for the receipt like TransactionMonitor, and alerts on failed or timed out
+    // transactions before the job turns them into errors
+    pub async fn monitor_transaction(
+        &self,
+        label: &str,
+        tx_hash: B256,
+    ) -> Result<TransactionReceipt> {
+        if let Some(relay) = &self.private_relay {
+            relay
+                .wait_for_inclusion(&self.client, &self.block_waiter, tx_hash)
+                .await?;
+        }
+        let mut receipt = self.monitor.monitor_transaction(tx_hash).await?;
+        receipt.estimated_cost_wei = self
+            .client
+            .recent_transactions()
+            .into_iter()
+            .find(|sent| sent.hash == tx_hash)
+            .and_then(|sent| sent.estimated_cost_wei);
+        if receipt.status != TransactionStatus::Timeout {
+            info!(
+                "💸 {} cost: {} wei (L2 {} gas at {} gwei, L1 data {} wei)",
+                label,
+                receipt.total_cost_wei(),
+                receipt.gas_used,
+                wei_to_gwei(receipt.effective_gas_price),
+                receipt.l1_fee.unwrap_or_default()
+            );
+        }
+        self.journal_outcome(&receipt);
+        let kind = match receipt.status {
+            TransactionStatus::Success => return Ok(receipt),
+            TransactionStatus::Failed => AlertKind::TransactionFailed,
+            TransactionStatus::Timeout => AlertKind::TransactionTimeout,
+        };
+        self.notifier
+            .notify(Alert::new(
+                kind,
+                self.chain_id,
+                format!("{:?}", tx_hash),
+                format!("{} transaction {:?}: {:?}", label, tx_hash, receipt.status),
+            ))
+            .await;
+        Ok(receipt)
+    }
+
+    fn journal_outcome(&self, receipt: &TransactionReceipt) {
+        let Some(journal) = self.client.journal() else {
+            return;
+        };
+        let status = match receipt.status {
+            TransactionStatus::Success => JournalStatus::Confirmed,
+            TransactionStatus::Failed => JournalStatus::Reverted,
+            // Still unfinished; the next run reconciles it
+            TransactionStatus::Timeout => return,
+        };
+        if let Err(e) = journal.record_outcome(receipt.hash, status) {
+            warn!("⚠️  Failed to journal outcome of {:?}: {}", receipt.hash, e);
+        }
+    }
+
+    // Settles transactions a previous run left unfinished before any new work starts:
+    // mined ones get their receipt status, ones whose nonce was taken by another tx are
+    // dropped, and ones still pending are rebroadcast and waited for. A tx that stays
+    // pending fails the run, since new sends would queue behind it.
+    //
+    // Runs once per context: jobs sharing it (daemon) wait for the first reconcile and
+    // then skip it, so it never reads entries another job is still writing.
+    pub async fn reconcile_journal(&self) -> Result<()> {
+        self.reconciled
+            .get_or_try_init(|| self.reconcile_unfinished())
+            .await?;
+        Ok(())
+    }
+
+    async fn reconcile_unfinished(&self) -> Result<()> {
+        let Some(journal) = self.client.journal() else {
+            return Ok(());
+        };
+        let unfinished = journal.unfinished()?;
+        if unfinished.is_empty() {
+            return journal.compact();
+        }
+
+        warn!(
+            "📒 Reconciling {} unfinished transaction(s) from a previous run",
+            unfinished.len()
+        );
+        let provider = self.client.provider();
+        let mined_nonce = provider
+            .get_transaction_count(self.client.keeper_address())
+            .latest()
+            .await
+            .map_err(|e| KeeperError::Rpc(e.to_string()))?;
+
+        for entry in unfinished {
+            let Some(hash) = entry.hash else {
+                info!(
+                    "   {} (nonce {:?}) was never signed",
+                    entry.intent, entry.nonce
+                );
+                journal.record_error(&entry.id, JournalStatus::Dropped, "never signed")?;
+                continue;
+            };
+
+            let receipt = provider
+                .get_transaction_receipt(hash)
+                .await
+                .map_err(|e| KeeperError::Rpc(e.to_string()))?;
+            if let Some(receipt) = receipt {
+                let status = if receipt.status() {
+                    JournalStatus::Confirmed
+                } else {
+                    JournalStatus::Reverted
+                };
+                info!("   {} {:?}: {:?}", entry.intent, hash, status);
+                journal.record_status(&entry.id, status)?;
+                continue;
+            }
+
+            if entry.nonce.is_some_and(|nonce| nonce < mined_nonce) {
+                info!("   {} {:?} was replaced", entry.intent, hash);
+                journal.record_error(
+                    &entry.id,
+                    JournalStatus::Dropped,
+                    "nonce used by another transaction",
+                )?;
+                continue;
+            }
+
+            if let Some(raw_tx) = &entry.raw_tx {
+                let raw_tx = hex::decode(raw_tx.trim_start_matches("0x"))?;
+                // Same route as the original send; entries from before jobs were
+                // journaled go out publicly
+                let client = match &entry.job {
+                    Some(job) => self.client_for(job),
+                    None => self.client.clone(),
+                };
+                // "already known" just means the node still has it
+                if let Err(e) = client.rebroadcast(hash, &raw_tx).await {
+                    info!("   Rebroadcast of {:?}: {}", hash, e);
+                }
+            }
+            let receipt = self
+                .monitor_transaction(&format!("Journaled {}", entry.intent), hash)
+                .await?;
+            if receipt.status == TransactionStatus::Timeout {
+                return Err(KeeperError::TransactionTimeout(format!(
+                    "{} {:?} from a previous run is still pending",
+                    entry.intent, hash
+                ))
+                .into());
+            }
+        }
+
+        journal.compact()
+    }
+
+    // Current keeper gas balance, also exported as a metric
+    pub async fn native_balance(&self) -> Result<U256> {
+        let balance = self
+            .client
+            .provider()
+            .get_balance(self.client.keeper_address())
+            .await?;
+        metrics().record_native_balance(self.chain_id, balance);
+        Ok(balance)
+    }
+
+    // Pre-run liveness check: a chain whose latest block is older than the configured
+    // age (sequencer down, every node stuck) would only leave transactions pending.
+    // Returns why the run should be skipped, after alerting.
+    pub async fn check_liveness(&self) -> Result<Option<String>> {
+        if self.max_block_age_seconds == 0 {
+            return Ok(None);
+        }
+        let liveness = self.client.check_liveness().await?;
+        metrics()
+            .head_block_age
+            .with_label_values(&[&self.chain_id.to_string()])
+            .set(liveness.age_seconds as f64);
+        for (endpoint, height) in &liveness.endpoint_heights {
+            match height {
+                Some(height) => info!("   {} at block {}", endpoint, height),
+                None => info!("   {} unreachable", endpoint),
+            }
+        }
+        if liveness.age_seconds <= self.max_block_age_seconds {
+            info!(
+                "💓 Chain {} live: block {} is {}s old",
+                self.chain_id, liveness.block_number, liveness.age_seconds
+            );
+            return Ok(None);
+        }
+
+        let reason = format!(
+            "latest block {} (best height {}) is {}s old, limit {}s",
+            liveness.block_number,
+            liveness.best_height,
+            liveness.age_seconds,
+            self.max_block_age_seconds
+        );
+        warn!("🧊 Chain {} stalled: {}", self.chain_id, reason);
+        self.notifier
+            .notify(Alert::new(
+                AlertKind::ChainStalled,
+                self.chain_id,
+                "chain",
+                format!("Chain {} stalled: {}", self.chain_id, reason),
+            ))
+            .await;
+        Ok(Some(reason))
+    }
+
+    // Gas ceiling for a job that is about to send. Returns true when the job should
+    // defer; dry runs report the decision without starting or ending a deferral.
+    pub async fn defer_for_gas(
+        &self,
+        job: &str,
+        dry_run: bool,
+        report: &RunRecorder,
+    ) -> Result<bool> {
+        let Some(ceiling) = self.gas_ceilings.get(job) else {
+            return Ok(false);
+        };
+        // The gas price stands in for the base fee on legacy chains
+        let base_fee = self.client.get_current_gas_price().await?;
+        report.read("base_fee_gwei", wei_to_gwei(base_fee));
+        let now = Utc::now();
+        let deferral = self.deferrals.get(job)?;
+
+        match decide(ceiling, base_fee, deferral, now) {
+            GasDecision::Proceed => {
+                if deferral.is_some() && !dry_run {
+                    info!(
+                        "⛽ Base fee {} gwei back under the {} gwei ceiling",
+                        wei_to_gwei(base_fee),
+                        ceiling.max_base_fee_gwei
+                    );
+                    self.deferrals.set(job, None)?;
+                }
+                Ok(false)
+            }
+            GasDecision::Urgent { deferred_for } => {
+                warn!(
+                    "🚨 Base fee {} gwei above the {} gwei ceiling, but deferred for {}s: acting anyway",
+                    wei_to_gwei(base_fee),
+                    ceiling.max_base_fee_gwei,
+                    deferred_for.as_secs()
+                );
+                report.decision(format!(
+                    "urgent: acting after {}s of deferral despite gas",
+                    deferred_for.as_secs()
+                ));
+                if !dry_run {
+                    self.deferrals.set(job, None)?;
+                }
+                Ok(false)
+            }
+            GasDecision::Defer { deferred_for } => {
+                info!(
+                    "⛽ Base fee {} gwei above the {} gwei ceiling, deferring (deferred for {}s)",
+                    wei_to_gwei(base_fee),
+                    ceiling.max_base_fee_gwei,
+                    deferred_for.as_secs()
+                );
+                report.decision("deferred: gas too high");
+                if !dry_run {
+                    let since = now - chrono::Duration::from_std(deferred_for)?;
+                    self.deferrals
+                        .set(job, Some(Deferral { since, last: now }))?;
+                }
+                Ok(true)
+            }
+        }
+    }
+
+    // Dynamic threshold: true when the pending yield doesn't cover the estimated cost
+    // of sending `txs` times the configured multiple
+    pub async fn below_profitability(
+        &self,
+        pending_yield: U256,
+        txs: Vec<TransactionRequest>,
+        report: &RunRecorder,
+    ) -> Result<bool> {
+        let Some(gate) = &self.profitability else {
+            return Ok(false);
+        };
+        let check = gate.check(&self.client, txs).await?;
+        report.read("estimated_gas", check.gas);
+        report.read("gas_cost_usdsc", check.cost_usdsc.to_string());
+        report.read("required_yield", check.required_yield.to_string());
+        if pending_yield >= check.required_yield {
+            return Ok(false);
+        }
+        info!(
+            "💸 Yield {} below gas cost {} x {} = {}, skipping",
+            pending_yield,
+            check.cost_usdsc,
+            gate.min_multiple(),
+            check.required_yield
+        );
+        report.decision("skipped: yield below gas cost multiple");
+        Ok(true)
+    }
+
+    // Best effort: a failed balance read never fails the job that triggered the check
+    pub async fn check_native_balance(&self) {
+        let Ok(balance) = self.native_balance().await else {
+            return;
+        };
+        let Some(min_eth) = self.notifier.settings().min_native_balance_eth else {
+            return;
+        };
+        let Ok(min_wei) = parse_ether(&min_eth.to_string()) else {
+            return;
+        };
+        if balance < min_wei {
+            self.notifier
+                .notify(Alert::new(
+                    AlertKind::LowNativeBalance,
+                    self.chain_id,
+                    self.client.keeper_address().to_string(),
+                    format!(
+                        "Keeper {} gas balance {} is below {}",
+                        self.client.keeper_address(),
+                        format_ether(balance),
+                        min_eth
+                    ),
+                ))
+                .await;
+        }
+    }
+}
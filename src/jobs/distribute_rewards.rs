@@ -1,56 +1,58 @@
 use crate::blockchain::BlockchainClient;
-use crate::config::ChainConfig;
-use crate::contracts::reward_redistributor::{RewardRedistributorContract, TxOverrides};
+use crate::config::{ChainConfig, DistributeSubmission};
+use crate::contracts::multicall3::{Multicall3Contract, MulticallUnavailable, MULTICALL3_ADDRESS};
+use crate::contracts::reward_redistributor::{
+    RewardRedistributorContract, SnapshotState, TxOverrides,
+};
 use crate::contracts::usdsc::USDSCContract;
-use crate::retry::{execute_with_retry, RetryConfig};
-use crate::transaction_monitor::{TransactionMonitor, TransactionStatus};
-use alloy::primitives::{Address, U256};
+use crate::error::KeeperError;
+use crate::jobs::{JobContext, JobOutcome};
+use crate::metrics::{metrics, u256_to_f64};
+use crate::notifier::{Alert, AlertKind};
+use crate::report::RunRecorder;
+use crate::retry::execute_with_retry;
+use crate::transaction_monitor::{TransactionReceipt, TransactionStatus};
+use alloy::primitives::{Address, B256, U256};
 use anyhow::Result;
 use std::str::FromStr;
-use std::time::Duration;
+use tracing::{error, info, warn};
 
 pub struct DistributeRewardsJob {
     config: ChainConfig,
     dry_run: bool,
+    report: RunRecorder,
 }
 
 impl DistributeRewardsJob {
     pub fn new(config: ChainConfig, dry_run: bool) -> Self {
-        Self { config, dry_run }
+        let report = RunRecorder::detached(&config);
+        Self {
+            config,
+            dry_run,
+            report,
+        }
     }
 
-    fn priority_fee_wei(&self) -> Option<u128> {
-        self.config
-            .transaction
-            .max_priority_fee_gwei
-            .map(|gwei| (gwei * 1_000_000_000.0) as u128)
+    pub fn with_report(mut self, report: RunRecorder) -> Self {
+        self.report = report;
+        self
     }
 
-    fn build_tx_overrides(&self, base_fee: u128) -> TxOverrides {
-        match self.priority_fee_wei() {
-            Some(tip) => TxOverrides {
-                max_priority_fee_per_gas: Some(tip),
-                // baseFee * 2 gives headroom for fee fluctuation across blocks
-                max_fee_per_gas: Some(base_fee * 2 + tip),
-                ..Default::default()
-            },
-            None => TxOverrides::default(),
-        }
-    }
-
-    async fn wait_for_next_block(client: &BlockchainClient) -> Result<()> {
-        let initial_block = client.get_block_number().await?;
-        println!("⏳ Waiting for next block (current: {})...", initial_block);
-
-        loop {
-            // Todo: This is specific to Soneium Block time, Need to this to config later
-            tokio::time::sleep(Duration::from_secs(3)).await; // Block time is 2 seconds , keeping a buffer of 1 second
-            let current_block = client.get_block_number().await?;
-            if current_block > initial_block {
-                println!("✅ New block confirmed: {}", current_block);
-                return Ok(());
-            }
-        }
+    #[allow(clippy::type_complexity)]
+    fn record_preview(&self, preview: &(U256, U256, U256, U256, U256, U256, U256, U256)) {
+        self.report.read(
+            "preview_distribute",
+            serde_json::json!({
+                "could_be_minted": preview.0.to_string(),
+                "fee_to_startale": preview.1.to_string(),
+                "to_earn": preview.2.to_string(),
+                "to_susdsc": preview.3.to_string(),
+                "to_startale_treasury": preview.4.to_string(),
+                "s_base": preview.5.to_string(),
+                "t_earn": preview.6.to_string(),
+                "t_yield": preview.7.to_string(),
+            }),
+        );
     }
 
     async fn get_current_timestamp(client: &BlockchainClient) -> Result<U256> {
@@ -65,36 +67,193 @@ impl DistributeRewardsJob {
         Ok(U256::from(timestamp))
     }
 
-    pub async fn execute(&self) -> Result<()> {
-        println!("🔍 Distribute Rewards Job Starting...");
+    // The aggregate3 target for `multicall` submission. It becomes msg.sender for both
+    // calls and holds the keeper role, so it must be a contract only the keeper can call:
+    // granting the role to the public Multicall3 would let anyone snapshot and distribute.
+    fn multicall_executor(&self) -> Result<Address> {
+        let address = self
+            .config
+            .distribute
+            .executor_address
+            .as_deref()
+            .ok_or_else(|| {
+                KeeperError::Config(
+                    "distribute.executor_address is required for multicall submission".to_string(),
+                )
+            })?;
+        let executor = BlockchainClient::parse_address(address)?;
+        if executor == MULTICALL3_ADDRESS {
+            return Err(KeeperError::Config(format!(
+                "distribute.executor_address can't be the public Multicall3 ({}): anyone could call through it with the keeper role",
+                MULTICALL3_ADDRESS
+            ))
+            .into());
+        }
+        Ok(executor)
+    }
 
-        let retry_config = RetryConfig::new(
-            self.config.retry.max_attempts,
-            Duration::from_secs(self.config.retry.base_delay_seconds),
-            Duration::from_secs(self.config.retry.max_delay_seconds),
-            self.config.retry.backoff_multiplier,
+    // Snapshot and distribute in one aggregate3 transaction, so they always share a block
+    async fn snapshot_and_distribute_batched(
+        &self,
+        ctx: &JobContext,
+        client: &BlockchainClient,
+        redistributor: &RewardRedistributorContract,
+    ) -> Result<JobOutcome> {
+        let executor = self.multicall_executor()?;
+        let multicall = Multicall3Contract::new(executor, client.clone());
+        let value_wei = &self.config.transaction.value_wei;
+        let calls = vec![
+            redistributor.snapshot_vault_tvls_request(value_wei, TxOverrides::default())?,
+            redistributor.distribute_request(value_wei, TxOverrides::default())?,
+        ];
+
+        info!(
+            "🚀 Snapshot + distribute in one aggregate3 call on {}...",
+            executor
         );
-
-        // KMS signing is required
-        let kms_config = self.config.kms.as_ref()
-            .ok_or_else(|| anyhow::anyhow!("KMS configuration is required. Please configure KMS settings in your config file or via CLI."))?;
-
-        println!("🔐 Using KMS signing with key: {}", kms_config.key_id);
-        let client = execute_with_retry(
+        let tx = execute_with_retry(
             || {
-                let rpc_url = self.config.chain.rpc_url.clone();
-                let chain_id = self.config.chain.chain_id;
-                let key_id = kms_config.key_id.clone();
+                let multicall = multicall.clone();
+                let calls = calls.clone();
                 async move {
-                    BlockchainClient::new(&rpc_url, chain_id, &key_id, &self.config).await
+                    multicall
+                        .aggregate3("snapshotVaultTVLs+distribute", &calls)
+                        .await
                 }
             },
-            &retry_config,
-            "Blockchain connection (KMS)",
-        ).await?;
+            ctx.retry_config(),
+            "Snapshot + distribute transaction",
+        )
+        .await?;
+
+        info!("✅ Snapshot + distribute transaction sent: {:?}", tx);
+
+        let receipt = ctx.monitor_transaction("Snapshot + distribute", tx).await?;
+        self.report
+            .transaction("aggregate3(snapshotVaultTVLs, distribute)", &receipt);
+        match receipt.status {
+            TransactionStatus::Success => {
+                info!(
+                    "🎉 Snapshot and distribute confirmed in block {}",
+                    receipt.block_number
+                );
+                info!("⛽ Gas used: {}", receipt.gas_used);
+                self.report
+                    .decision("snapshot taken and distributed in one transaction");
+                Ok(JobOutcome::Completed)
+            }
+            TransactionStatus::Failed => Err(KeeperError::TransactionReverted(
+                "Snapshot + distribute transaction failed".to_string(),
+            )
+            .into()),
+            TransactionStatus::Timeout => Err(KeeperError::TransactionTimeout(
+                "Snapshot + distribute transaction monitoring timeout".to_string(),
+            )
+            .into()),
+        }
+    }
+
+    // Both signed up front, so distribute is ready the moment the snapshot is confirmed
+    // and the nonces keep it after the snapshot. It is only broadcast once the snapshot
+    // is mined, for contracts that refuse both in one block.
+    async fn snapshot_and_distribute_presigned(
+        &self,
+        ctx: &JobContext,
+        client: &BlockchainClient,
+        redistributor: &RewardRedistributorContract,
+    ) -> Result<JobOutcome> {
+        let value_wei = &self.config.transaction.value_wei;
+        let snapshot =
+            redistributor.snapshot_vault_tvls_request(value_wei, TxOverrides::default())?;
+        let distribute = redistributor.distribute_request(
+            value_wei,
+            TxOverrides {
+                gas_limit: Some(self.config.distribute.distribute_gas_limit),
+                ..Default::default()
+            },
+        )?;
+
+        info!("🚀 Presigning snapshotVaultTVLs + distribute...");
+        let mut batch = client
+            .presign(vec![
+                ("snapshotVaultTVLs", snapshot),
+                ("distribute", distribute),
+            ])
+            .await?;
+        let snapshot_tx = client.send_next_presigned(&mut batch).await?;
+
+        // The snapshot's block is sealed once its receipt exists, so distribute sent now
+        // can only land in a later block
+        let snapshot_receipt = match self.confirm_snapshot(ctx, snapshot_tx).await {
+            Ok(receipt) => receipt,
+            Err(e) => {
+                client.discard_presigned(batch, &e).await;
+                return Err(e);
+            }
+        };
+        let dist_tx = client.send_next_presigned(&mut batch).await?;
+
+        let dist_receipt = ctx.monitor_transaction("Distribute", dist_tx).await?;
+        self.report.transaction("distribute", &dist_receipt);
+        match dist_receipt.status {
+            TransactionStatus::Success => {
+                let block_delta = dist_receipt.block_number - snapshot_receipt.block_number;
+                info!(
+                    "🎉 Distribute confirmed in block {} ({} block(s) after snapshot)",
+                    dist_receipt.block_number, block_delta
+                );
+                info!("⛽ Gas used: {}", dist_receipt.gas_used);
+                self.report
+                    .decision("snapshot taken and distributed (presigned)");
+                Ok(JobOutcome::Completed)
+            }
+            TransactionStatus::Failed => Err(KeeperError::TransactionReverted(format!(
+                "Distribute transaction failed (block {}, snapshot in block {})",
+                dist_receipt.block_number, snapshot_receipt.block_number
+            ))
+            .into()),
+            TransactionStatus::Timeout => Err(KeeperError::TransactionTimeout(
+                "Distribute transaction monitoring timeout".to_string(),
+            )
+            .into()),
+        }
+    }
+
+    async fn confirm_snapshot(
+        &self,
+        ctx: &JobContext,
+        snapshot_tx: B256,
+    ) -> Result<TransactionReceipt> {
+        let receipt = ctx.monitor_transaction("Snapshot", snapshot_tx).await?;
+        self.report.transaction("snapshotVaultTVLs", &receipt);
+        match receipt.status {
+            TransactionStatus::Success => {
+                info!("🎉 Snapshot confirmed in block {}", receipt.block_number);
+                Ok(receipt)
+            }
+            TransactionStatus::Failed => Err(KeeperError::TransactionReverted(
+                "Snapshot transaction failed".to_string(),
+            )
+            .into()),
+            TransactionStatus::Timeout => Err(KeeperError::TransactionTimeout(
+                "Snapshot transaction monitoring timeout".to_string(),
+            )
+            .into()),
+        }
+    }
+
+    pub async fn execute(&self, ctx: &JobContext) -> Result<JobOutcome> {
+        info!("🔍 Distribute Rewards Job Starting...");
+
+        let client = &ctx.client_for("distribute-rewards");
+        let retry_config = ctx.retry_config();
+        // A misconfigured executor fails the run up front, not once a snapshot is due
+        if self.config.distribute.submission == DistributeSubmission::Multicall {
+            self.multicall_executor()?;
+        }
 
         let block_number = client.get_block_number().await?;
-        println!("📦 Current block: {}", block_number);
+        info!("📦 Current block: {}", block_number);
 
         // First check USDSC yield (reusing logic from claim_yield.rs)
         let usdsc_contract = USDSCContract::new(
@@ -105,20 +264,26 @@ impl DistributeRewardsJob {
 
         // Check pending yield (no retry for lightweight read operations)
         let pending_yield = usdsc_contract.get_pending_yield().await?;
-        println!("💰 Pending yield: {}", pending_yield);
+        info!("💰 Pending yield: {}", pending_yield);
+        metrics().record_pending_yield(self.config.chain.chain_id, pending_yield);
 
         // Check if yield is above threshold
         let min_threshold = U256::from_str(&self.config.thresholds.min_yield_threshold)?;
+        self.report.keeper_address(client.keeper_address());
+        self.report.read("pending_yield", pending_yield.to_string());
+        self.report
+            .read("min_yield_threshold", min_threshold.to_string());
 
         if pending_yield < min_threshold {
-            println!(
+            info!(
                 "⏳ Yield below threshold ({} < {}), skipping distribution",
                 pending_yield, min_threshold
             );
-            return Ok(());
+            self.report.decision("skipped: yield below threshold");
+            return Ok(JobOutcome::NoOp);
         }
 
-        println!(
+        info!(
             "💰 Yield above threshold ({} >= {}), proceeding with distribution...",
             pending_yield, min_threshold
         );
@@ -132,47 +297,109 @@ impl DistributeRewardsJob {
                 client.clone(),
             );
 
-            // ===== STEP 1: Check snapshot state =====
-            println!("📸 Checking snapshot state...");
+            // Costed as snapshot + distribute, the worst case
+            let value_wei = &self.config.transaction.value_wei;
+            let txs = vec![
+                redistributor_contract
+                    .snapshot_vault_tvls_request(value_wei, TxOverrides::default())?,
+                redistributor_contract.distribute_request(value_wei, TxOverrides::default())?,
+            ];
+            if ctx
+                .below_profitability(pending_yield, txs, &self.report)
+                .await?
+            {
+                return Ok(JobOutcome::NoOp);
+            }
 
-            let (
+            if ctx
+                .defer_for_gas("distribute-rewards", self.dry_run, &self.report)
+                .await?
+            {
+                return Ok(JobOutcome::Deferred);
+            }
+
+            // ===== STEP 1: Check snapshot state =====
+            info!("📸 Checking snapshot state...");
+
+            // One aggregate3 read at a single block; one call each on a chain without
+            // Multicall3. Any other failure fails the run like the single reads would.
+            let state = match redistributor_contract.snapshot_state(None).await {
+                Ok(state) => state,
+                Err(e) if e.downcast_ref::<MulticallUnavailable>().is_some() => {
+                    warn!("⚠️  {}, reading one by one", e);
+                    let (
+                        last_snapshot_timestamp,
+                        last_snapshot_block,
+                        snapshot_max_age,
+                        last_susdsc_tvl,
+                        last_earn_tvl,
+                        block_number,
+                        block_timestamp,
+                    ) = tokio::try_join!(
+                        redistributor_contract.last_snapshot_timestamp(),
+                        redistributor_contract.last_snapshot_block_number(),
+                        redistributor_contract.snapshot_max_age(),
+                        redistributor_contract.last_susdsc_tvl(),
+                        redistributor_contract.last_earn_tvl(),
+                        client.get_block_number(),
+                        Self::get_current_timestamp(client),
+                    )?;
+                    SnapshotState {
+                        last_snapshot_timestamp,
+                        last_snapshot_block,
+                        snapshot_max_age,
+                        last_susdsc_tvl,
+                        last_earn_tvl,
+                        block_number,
+                        block_timestamp,
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+            let SnapshotState {
                 last_snapshot_timestamp,
                 last_snapshot_block,
-                max_age_seconds,
+                snapshot_max_age: max_age_seconds,
                 last_susdsc_tvl,
                 last_earn_tvl,
-                current_block,
-                current_timestamp,
-                base_fee,
-            ) = tokio::try_join!(
-                redistributor_contract.last_snapshot_timestamp(),
-                redistributor_contract.last_snapshot_block_number(),
-                redistributor_contract.snapshot_max_age(),
-                redistributor_contract.last_susdsc_tvl(),
-                redistributor_contract.last_earn_tvl(),
-                client.get_block_number(),
-                Self::get_current_timestamp(&client),
-                client.get_base_fee_per_gas(),
-            )?;
-
-            println!("   Last snapshot timestamp: {}", last_snapshot_timestamp);
-            println!("   Last snapshot block: {}", last_snapshot_block);
-            println!("   Last sUSDSC vault TVL (snapshot): {}", last_susdsc_tvl);
-            println!("   Last Earn vault TVL (snapshot): {}", last_earn_tvl);
-            println!("   Max age: {}s", max_age_seconds);
-            println!("   Current block: {}", current_block);
-            println!("   Current timestamp: {}", current_timestamp);
+                block_number: current_block,
+                block_timestamp: current_timestamp,
+            } = state;
+
+            info!("   Last snapshot timestamp: {}", last_snapshot_timestamp);
+            info!("   Last snapshot block: {}", last_snapshot_block);
+            info!("   Last sUSDSC vault TVL (snapshot): {}", last_susdsc_tvl);
+            info!("   Last Earn vault TVL (snapshot): {}", last_earn_tvl);
+            info!("   Max age: {}s", max_age_seconds);
+            info!("   Current block: {}", current_block);
+            info!("   Current timestamp: {}", current_timestamp);
+            self.report.read(
+                "snapshot",
+                serde_json::json!({
+                    "last_snapshot_timestamp": last_snapshot_timestamp.to_string(),
+                    "last_snapshot_block": last_snapshot_block.to_string(),
+                    "max_age_seconds": max_age_seconds.to_string(),
+                    "last_susdsc_tvl": last_susdsc_tvl.to_string(),
+                    "last_earn_tvl": last_earn_tvl.to_string(),
+                    "current_block": current_block,
+                    "current_timestamp": current_timestamp.to_string(),
+                }),
+            );
 
             let current_block_u256 = U256::from(current_block);
             let needs_snapshot =
                 if last_snapshot_timestamp == U256::ZERO || last_snapshot_block == U256::ZERO {
-                    println!("   ⚠️  No snapshot exists");
+                    warn!("   ⚠️  No snapshot exists");
                     true
                 } else {
                     // Check if snapshot is too old (time-based)
                     let snapshot_age = current_timestamp.saturating_sub(last_snapshot_timestamp);
+                    metrics()
+                        .snapshot_age
+                        .with_label_values(&[&self.config.chain.chain_id.to_string()])
+                        .set(u256_to_f64(snapshot_age));
                     if snapshot_age > max_age_seconds {
-                        println!(
+                        warn!(
                             "   ⚠️  Snapshot expired (age {}s > max {}s)",
                             snapshot_age, max_age_seconds
                         );
@@ -180,14 +407,14 @@ impl DistributeRewardsJob {
                     } else {
                         // Check if snapshot is in same block (block-based)
                         if current_block_u256 <= last_snapshot_block {
-                            println!(
+                            warn!(
                             "   ⚠️  Snapshot in same or future block (current {} <= snapshot {})",
                             current_block, last_snapshot_block
                         );
                             // We'll wait for next block below
                             false
                         } else {
-                            println!(
+                            info!(
                                 "   ✅ Snapshot is valid (block {} > {}, age {}s <= max {}s)",
                                 current_block, last_snapshot_block, snapshot_age, max_age_seconds
                             );
@@ -196,43 +423,72 @@ impl DistributeRewardsJob {
                     }
                 };
 
-            let timeout_gas_used = U256::from_str(&self.config.monitoring.timeout_gas_used)?;
-            let monitor = TransactionMonitor::new_with_timeout_values(
-                client.provider(),
-                Duration::from_secs(self.config.monitoring.transaction_timeout_seconds),
-                Duration::from_secs(self.config.monitoring.poll_interval_seconds),
-                self.config.monitoring.timeout_block_number,
-                timeout_gas_used,
-            );
+            // Alert when the snapshot has been stale for longer than the configured limit,
+            // e.g. because earlier runs kept failing
+            if let Some(alert_age) = self.config.alerts.max_snapshot_age_seconds {
+                let snapshot_age = current_timestamp.saturating_sub(last_snapshot_timestamp);
+                if last_snapshot_timestamp > U256::ZERO && snapshot_age > U256::from(alert_age) {
+                    ctx.notifier()
+                        .notify(Alert::new(
+                            AlertKind::SnapshotTooOld,
+                            self.config.chain.chain_id,
+                            redistributor_addr.clone(),
+                            format!(
+                                "RewardRedistributor {} snapshot is {}s old (alert threshold {}s)",
+                                redistributor_addr, snapshot_age, alert_age
+                            ),
+                        ))
+                        .await;
+                }
+            }
 
             // ===== STEP 2: Take snapshot if needed =====
             if needs_snapshot {
-                println!("📸 Taking new snapshot (sUSDSC + Earn vault TVLs)...");
+                info!("📸 Taking new snapshot (sUSDSC + Earn vault TVLs)...");
 
                 if self.dry_run {
-                    println!("✅ DRY RUN: Would call snapshotVaultTVLs()");
-                    return Ok(());
+                    info!("✅ DRY RUN: Would call snapshotVaultTVLs()");
+                    self.report.decision("dry run: would take snapshot");
+                    return Ok(JobOutcome::Completed);
+                }
+
+                match self.config.distribute.submission {
+                    DistributeSubmission::Sequential => {}
+                    DistributeSubmission::Multicall => {
+                        return self
+                            .snapshot_and_distribute_batched(ctx, client, &redistributor_contract)
+                            .await;
+                    }
+                    DistributeSubmission::Presigned => {
+                        return self
+                            .snapshot_and_distribute_presigned(ctx, client, &redistributor_contract)
+                            .await;
+                    }
                 }
 
-                let snapshot_overrides = self.build_tx_overrides(base_fee);
                 let snapshot_tx = execute_with_retry(
                     || {
                         let contract = redistributor_contract.clone();
                         let value_wei = self.config.transaction.value_wei.clone();
-                        let overrides = snapshot_overrides.clone();
-                        async move { contract.snapshot_vault_tvls(&value_wei, overrides).await }
+                        async move {
+                            contract
+                                .snapshot_vault_tvls(&value_wei, TxOverrides::default())
+                                .await
+                        }
                     },
-                    &retry_config,
+                    retry_config,
                     "Snapshot transaction",
                 )
                 .await?;
 
-                println!("✅ Snapshot transaction sent: {:?}", snapshot_tx);
+                info!("✅ Snapshot transaction sent: {:?}", snapshot_tx);
 
-                let snapshot_receipt = monitor.monitor_transaction(snapshot_tx).await?;
+                let snapshot_receipt = ctx.monitor_transaction("Snapshot", snapshot_tx).await?;
+                self.report
+                    .transaction("snapshotVaultTVLs", &snapshot_receipt);
                 match snapshot_receipt.status {
                     TransactionStatus::Success => {
-                        println!(
+                        info!(
                             "🎉 Snapshot confirmed in block {}",
                             snapshot_receipt.block_number
                         );
@@ -241,128 +497,157 @@ impl DistributeRewardsJob {
                             redistributor_contract.last_susdsc_tvl(),
                             redistributor_contract.last_earn_tvl(),
                         )?;
-                        println!("📸 New sUSDSC vault TVL: {}", new_susdsc);
-                        println!("📸 New Earn vault TVL: {}", new_earn);
+                        info!("📸 New sUSDSC vault TVL: {}", new_susdsc);
+                        info!("📸 New Earn vault TVL: {}", new_earn);
+                        self.report.decision("snapshot taken");
                     }
                     TransactionStatus::Failed => {
-                        return Err(anyhow::anyhow!("Snapshot transaction failed"));
+                        return Err(KeeperError::TransactionReverted(
+                            "Snapshot transaction failed".to_string(),
+                        )
+                        .into());
                     }
                     TransactionStatus::Timeout => {
-                        return Err(anyhow::anyhow!("Snapshot transaction monitoring timeout"));
+                        return Err(KeeperError::TransactionTimeout(
+                            "Snapshot transaction monitoring timeout".to_string(),
+                        )
+                        .into());
                     }
                 }
 
                 // ===== STEP 3: Preview =====
-                println!("📊 Previewing distribution...");
+                info!("📊 Previewing distribution...");
                 let preview = redistributor_contract.preview_distribute().await?;
-                println!("📊 Distribution preview:");
-                println!("   Could be minted: {}", preview.0);
-                println!("   Fee to Startale: {}", preview.1);
-                println!("   To Earn: {}", preview.2);
-                println!("   To sUSDSC: {}", preview.3);
-                println!("   To Startale Treasury: {}", preview.4);
+                info!("📊 Distribution preview:");
+                info!("   Could be minted: {}", preview.0);
+                info!("   Fee to Startale: {}", preview.1);
+                info!("   To Earn: {}", preview.2);
+                info!("   To sUSDSC: {}", preview.3);
+                info!("   To Startale Treasury: {}", preview.4);
+                self.record_preview(&preview);
 
                 // ===== STEP 4: Distribute — submitted immediately after snapshot confirms =====
-                println!("🚀 Distributing immediately after snapshot (targeting next block)...");
-
-                let dist_base_fee = client.get_base_fee_per_gas().await?;
-                let dist_overrides = self.build_tx_overrides(dist_base_fee);
+                info!("🚀 Distributing immediately after snapshot (targeting next block)...");
 
                 let dist_tx = execute_with_retry(
                     || {
                         let contract = redistributor_contract.clone();
                         let value_wei = self.config.transaction.value_wei.clone();
-                        let overrides = dist_overrides.clone();
-                        async move { contract.distribute(&value_wei, overrides).await }
+                        async move {
+                            contract
+                                .distribute(&value_wei, TxOverrides::default())
+                                .await
+                        }
                     },
-                    &retry_config,
+                    retry_config,
                     "Distribute transaction",
                 )
                 .await?;
 
-                println!("✅ Distribute transaction sent: {:?}", dist_tx);
+                info!("✅ Distribute transaction sent: {:?}", dist_tx);
 
-                let dist_receipt = monitor.monitor_transaction(dist_tx).await?;
+                let dist_receipt = ctx.monitor_transaction("Distribute", dist_tx).await?;
+                self.report.transaction("distribute", &dist_receipt);
                 match dist_receipt.status {
                     TransactionStatus::Success => {
                         let block_delta = dist_receipt.block_number - snapshot_receipt.block_number;
-                        println!(
+                        info!(
                             "🎉 Distribute confirmed in block {} ({} block(s) after snapshot)",
                             dist_receipt.block_number, block_delta
                         );
-                        println!("⛽ Gas used: {}", dist_receipt.gas_used);
+                        info!("⛽ Gas used: {}", dist_receipt.gas_used);
+                        self.report.decision("distributed");
                     }
                     TransactionStatus::Failed => {
-                        return Err(anyhow::anyhow!("Distribute transaction failed"));
+                        return Err(KeeperError::TransactionReverted(
+                            "Distribute transaction failed".to_string(),
+                        )
+                        .into());
                     }
                     TransactionStatus::Timeout => {
-                        return Err(anyhow::anyhow!("Distribute transaction monitoring timeout"));
+                        return Err(KeeperError::TransactionTimeout(
+                            "Distribute transaction monitoring timeout".to_string(),
+                        )
+                        .into());
                     }
                 }
             } else {
                 // Snapshot is valid — wait only if we're in the same block as the snapshot
                 if current_block_u256 <= last_snapshot_block {
-                    println!("⏳ Waiting for next block before distributing...");
-                    Self::wait_for_next_block(&client).await?;
+                    info!("⏳ Waiting for next block before distributing...");
+                    ctx.wait_for_next_block().await?;
                 }
 
                 // ===== STEP 3: Preview =====
-                println!("📊 Previewing distribution...");
+                info!("📊 Previewing distribution...");
                 let preview = redistributor_contract.preview_distribute().await?;
-                println!("📊 Distribution preview:");
-                println!("   Could be minted: {}", preview.0);
-                println!("   Fee to Startale: {}", preview.1);
-                println!("   To Earn: {}", preview.2);
-                println!("   To sUSDSC: {}", preview.3);
-                println!("   To Startale Treasury: {}", preview.4);
+                info!("📊 Distribution preview:");
+                info!("   Could be minted: {}", preview.0);
+                info!("   Fee to Startale: {}", preview.1);
+                info!("   To Earn: {}", preview.2);
+                info!("   To sUSDSC: {}", preview.3);
+                info!("   To Startale Treasury: {}", preview.4);
+                self.record_preview(&preview);
 
                 if self.dry_run {
-                    println!("✅ DRY RUN: Would call distribute() on RewardRedistributor");
-                    return Ok(());
+                    info!("✅ DRY RUN: Would call distribute() on RewardRedistributor");
+                    self.report.decision("dry run: would distribute");
+                    return Ok(JobOutcome::Completed);
                 }
 
                 // ===== STEP 4: Distribute =====
-                println!("🚀 Calling distribute() on RewardRedistributor...");
-                let dist_base_fee = client.get_base_fee_per_gas().await?;
-                let dist_overrides = self.build_tx_overrides(dist_base_fee);
-
+                info!("🚀 Calling distribute() on RewardRedistributor...");
                 let dist_tx = execute_with_retry(
                     || {
                         let contract = redistributor_contract.clone();
                         let value_wei = self.config.transaction.value_wei.clone();
-                        let overrides = dist_overrides.clone();
-                        async move { contract.distribute(&value_wei, overrides).await }
+                        async move {
+                            contract
+                                .distribute(&value_wei, TxOverrides::default())
+                                .await
+                        }
                     },
-                    &retry_config,
+                    retry_config,
                     "Distribute transaction",
                 )
                 .await?;
 
-                println!("✅ Distribute transaction sent: {:?}", dist_tx);
+                info!("✅ Distribute transaction sent: {:?}", dist_tx);
 
-                let dist_receipt = monitor.monitor_transaction(dist_tx).await?;
+                let dist_receipt = ctx.monitor_transaction("Distribute", dist_tx).await?;
+                self.report.transaction("distribute", &dist_receipt);
                 match dist_receipt.status {
                     TransactionStatus::Success => {
-                        println!(
+                        info!(
                             "🎉 Distribute confirmed in block {}",
                             dist_receipt.block_number
                         );
-                        println!("⛽ Gas used: {}", dist_receipt.gas_used);
+                        info!("⛽ Gas used: {}", dist_receipt.gas_used);
+                        self.report.decision("distributed");
                     }
                     TransactionStatus::Failed => {
-                        println!("❌ Distribute transaction failed");
-                        return Err(anyhow::anyhow!("Transaction failed"));
+                        error!("❌ Distribute transaction failed");
+                        return Err(KeeperError::TransactionReverted(
+                            "Transaction failed".to_string(),
+                        )
+                        .into());
                     }
                     TransactionStatus::Timeout => {
-                        println!("⏰ Distribute transaction monitoring timeout");
-                        return Err(anyhow::anyhow!("Transaction monitoring timeout"));
+                        info!("⏰ Distribute transaction monitoring timeout");
+                        return Err(KeeperError::TransactionTimeout(
+                            "Transaction monitoring timeout".to_string(),
+                        )
+                        .into());
                     }
                 }
             }
         } else {
-            println!("⚠️ No RewardRedistributor address configured");
+            warn!("⚠️ No RewardRedistributor address configured");
+            self.report
+                .decision("skipped: no RewardRedistributor configured");
+            return Ok(JobOutcome::NoOp);
         }
 
-        Ok(())
+        Ok(JobOutcome::Completed)
     }
 }
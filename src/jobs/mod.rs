@@ -1,8 +1,34 @@
 pub mod boost_rewards;
 pub mod boost_rewards_s3;
 pub mod claim_yield;
+pub mod context;
 pub mod distribute_rewards;
 
 pub use boost_rewards::BoostRewardsJob;
 pub use claim_yield::ClaimYieldJob;
+pub use context::JobContext;
 pub use distribute_rewards::DistributeRewardsJob;
+
+use crate::error::{EXIT_CHAIN_STALLED, EXIT_DEFERRED, EXIT_NO_OP, EXIT_SUCCESS};
+
+// Whether a successful run did any work; runs with nothing to do get their own exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobOutcome {
+    Completed,
+    NoOp,
+    // Skipped before doing anything because the chain isn't producing blocks
+    ChainStalled,
+    // Work is due but the base fee is above the job's ceiling
+    Deferred,
+}
+
+impl JobOutcome {
+    pub fn exit_code(self) -> u8 {
+        match self {
+            JobOutcome::Completed => EXIT_SUCCESS,
+            JobOutcome::NoOp => EXIT_NO_OP,
+            JobOutcome::ChainStalled => EXIT_CHAIN_STALLED,
+            JobOutcome::Deferred => EXIT_DEFERRED,
+        }
+    }
+}
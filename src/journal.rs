@@ -0,0 +1,322 @@
+use crate::config::ChainConfig;
+use crate::run_lock::RunLock;
+use alloy::primitives::B256;
+use anyhow::Result;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::info;
+
+// Finished entries older than this are dropped when the journal is compacted
+const RETENTION_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalStatus {
+    // Intent recorded, nothing signed yet
+    Pending,
+    // Signed; the raw tx may or may not have reached the node
+    Signed,
+    // Accepted by the node, waiting for a receipt
+    Sent,
+    Confirmed,
+    Reverted,
+    // Never broadcast, rejected by the node, or its nonce was used by another tx
+    Dropped,
+}
+
+impl JournalStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            JournalStatus::Confirmed | JournalStatus::Reverted | JournalStatus::Dropped
+        )
+    }
+}
+
+// One line of the journal file. Only `id`, `at` and `status` are always set; the
+// state of an entry is all of its lines merged in order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct JournalRecord {
+    id: String,
+    at: DateTime<Utc>,
+    status: JournalStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    intent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    job: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    nonce: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hash: Option<B256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    raw_tx: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl JournalRecord {
+    fn new(id: &str, status: JournalStatus) -> Self {
+        Self {
+            id: id.to_string(),
+            at: Utc::now(),
+            status,
+            intent: None,
+            job: None,
+            nonce: None,
+            hash: None,
+            raw_tx: None,
+            error: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JournalEntry {
+    pub id: String,
+    pub intent: String,
+    // Job that sent it, so a rebroadcast takes the same route (e.g. its private relay)
+    pub job: Option<String>,
+    pub nonce: Option<u64>,
+    pub hash: Option<B256>,
+    pub raw_tx: Option<String>, // 0x-prefixed EIP-2718 encoding
+    pub status: JournalStatus,
+    pub error: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl JournalEntry {
+    fn apply(&mut self, record: JournalRecord) {
+        self.status = record.status;
+        self.updated_at = record.at;
+        if let Some(intent) = record.intent {
+            self.intent = intent;
+        }
+        self.job = record.job.or(self.job.take());
+        self.nonce = record.nonce.or(self.nonce);
+        self.hash = record.hash.or(self.hash);
+        self.raw_tx = record.raw_tx.or(self.raw_tx.take());
+        self.error = record.error.or(self.error.take());
+    }
+
+    fn to_record(&self) -> JournalRecord {
+        JournalRecord {
+            id: self.id.clone(),
+            at: self.updated_at,
+            status: self.status,
+            intent: Some(self.intent.clone()),
+            job: self.job.clone(),
+            nonce: self.nonce,
+            hash: self.hash,
+            raw_tx: self.raw_tx.clone(),
+            error: self.error.clone(),
+        }
+    }
+}
+
+// Write-ahead log of every transaction the keeper sends: one JSON line per state
+// change, synced to disk before the next step, so a run that dies between two
+// transactions leaves enough behind for the next run to find out what happened.
+pub struct TxJournal {
+    path: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl TxJournal {
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| {
+                anyhow::anyhow!("Failed to create journal dir {}: {}", dir.display(), e)
+            })?;
+        }
+        Ok(Self {
+            path,
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    // One journal per chain and signing key, like the run lock: that is the nonce space
+    pub fn from_config(config: &ChainConfig) -> Result<Self> {
+        Self::open(
+            config
+                .journal
+                .dir()
+                .join(format!("{}.jsonl", RunLock::lock_name(config))),
+        )
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn append(&self, record: &JournalRecord) -> Result<()> {
+        let line = serde_json::to_string(record)?;
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| {
+                anyhow::anyhow!("Failed to open journal {}: {}", self.path.display(), e)
+            })?;
+        // Terminate a line torn by a crash so it doesn't swallow this record
+        if file.metadata()?.len() > 0 {
+            let mut last = [0u8; 1];
+            file.seek(SeekFrom::End(-1))?;
+            file.read_exact(&mut last)?;
+            if last[0] != b'\n' {
+                file.write_all(b"\n")?;
+            }
+        }
+        writeln!(file, "{}", line)?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    // Records what is about to be sent, and by which job when known, and returns the
+    // entry id for the later steps
+    pub fn record_intent(&self, job: Option<&str>, intent: &str, nonce: u64) -> Result<String> {
+        let id = format!("{}-{}", Utc::now().format("%Y%m%dT%H%M%S%.3fZ"), nonce);
+        let mut record = JournalRecord::new(&id, JournalStatus::Pending);
+        record.intent = Some(intent.to_string());
+        record.job = job.map(str::to_string);
+        record.nonce = Some(nonce);
+        self.append(&record)?;
+        Ok(id)
+    }
+
+    pub fn record_sent(&self, id: &str, hash: B256) -> Result<()> {
+        let mut record = JournalRecord::new(id, JournalStatus::Sent);
+        record.hash = Some(hash);
+        self.append(&record)
+    }
+
+    pub fn record_signed(&self, id: &str, hash: B256, raw_tx: &[u8]) -> Result<()> {
+        let mut record = JournalRecord::new(id, JournalStatus::Signed);
+        record.hash = Some(hash);
+        record.raw_tx = Some(format!("0x{}", hex::encode(raw_tx)));
+        self.append(&record)
+    }
+
+    pub fn record_status(&self, id: &str, status: JournalStatus) -> Result<()> {
+        self.append(&JournalRecord::new(id, status))
+    }
+
+    pub fn record_error(&self, id: &str, status: JournalStatus, error: &str) -> Result<()> {
+        let mut record = JournalRecord::new(id, status);
+        record.error = Some(error.to_string());
+        self.append(&record)
+    }
+
+    // Final status for the entry that sent `hash`, if it is journaled
+    pub fn record_outcome(&self, hash: B256, status: JournalStatus) -> Result<()> {
+        let entry = self
+            .entries()?
+            .into_iter()
+            .rev()
+            .find(|entry| entry.hash == Some(hash));
+        match entry {
+            Some(entry) if entry.status != status => self.record_status(&entry.id, status),
+            _ => Ok(()),
+        }
+    }
+
+    // Current state of every entry, oldest first. A torn last line from a crash
+    // mid-write is skipped.
+    pub fn entries(&self) -> Result<Vec<JournalEntry>> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(anyhow::anyhow!(
+                    "Failed to read journal {}: {}",
+                    self.path.display(),
+                    e
+                ))
+            }
+        };
+
+        let mut order = Vec::new();
+        let mut entries: BTreeMap<String, JournalEntry> = BTreeMap::new();
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            let Ok(record) = serde_json::from_str::<JournalRecord>(line) else {
+                continue;
+            };
+            let entry = entries.entry(record.id.clone()).or_insert_with(|| {
+                order.push(record.id.clone());
+                JournalEntry {
+                    id: record.id.clone(),
+                    intent: String::new(),
+                    job: None,
+                    nonce: None,
+                    hash: None,
+                    raw_tx: None,
+                    status: record.status,
+                    error: None,
+                    updated_at: record.at,
+                }
+            });
+            entry.apply(record);
+        }
+
+        Ok(order
+            .into_iter()
+            .filter_map(|id| entries.remove(&id))
+            .collect())
+    }
+
+    // Most recent entry for `intent` updated at or after `since`
+    pub fn latest(&self, intent: &str, since: DateTime<Utc>) -> Result<Option<JournalEntry>> {
+        Ok(self
+            .entries()?
+            .into_iter()
+            .rev()
+            .find(|entry| entry.intent == intent && entry.updated_at >= since))
+    }
+
+    pub fn unfinished(&self) -> Result<Vec<JournalEntry>> {
+        Ok(self
+            .entries()?
+            .into_iter()
+            .filter(|entry| !entry.status.is_finished())
+            .collect())
+    }
+
+    // Rewrites the file with one line per entry, dropping finished entries past the
+    // retention window. Written to a temp file and renamed, so a crash keeps the old one.
+    pub fn compact(&self) -> Result<()> {
+        let cutoff = Utc::now() - ChronoDuration::days(RETENTION_DAYS);
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let entries = self.entries()?;
+        let before = entries.len();
+        let kept: Vec<_> = entries
+            .into_iter()
+            .filter(|entry| !entry.status.is_finished() || entry.updated_at > cutoff)
+            .collect();
+        if kept.len() == before {
+            return Ok(());
+        }
+
+        let mut content = String::new();
+        for entry in &kept {
+            content.push_str(&serde_json::to_string(&entry.to_record())?);
+            content.push('\n');
+        }
+        let tmp = self.path.with_extension("jsonl.tmp");
+        fs::write(&tmp, content)?;
+        fs::rename(&tmp, &self.path)?;
+        info!(
+            "🗜️  Compacted journal {} ({} -> {} entries)",
+            self.path.display(),
+            before,
+            kept.len()
+        );
+        Ok(())
+    }
+}
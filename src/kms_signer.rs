@@ -1,7 +1,9 @@
+use crate::error::KeeperError;
 use alloy::signers::{aws::AwsSigner, Signer};
 use anyhow::Result;
 use aws_config::BehaviorVersion;
 use aws_sdk_kms::Client as KmsClient;
+use tracing::info;
 
 #[derive(Clone)]
 pub struct KmsSigner {
@@ -10,7 +12,7 @@ pub struct KmsSigner {
 
 impl KmsSigner {
     pub async fn new(key_id: String, region: String, chain_id: u64) -> Result<Self> {
-        println!("🔐 Initializing AWS KMS signer...");
+        info!("🔐 Initializing AWS KMS signer...");
 
         let config = aws_config::defaults(BehaviorVersion::latest())
             .region(aws_config::Region::new(region.clone()))
@@ -20,10 +22,10 @@ impl KmsSigner {
 
         let signer = AwsSigner::new(kms_client, key_id, Some(chain_id))
             .await
-            .map_err(|e| anyhow::anyhow!("Failed to create AWS signer: {}", e))?;
+            .map_err(|e| KeeperError::Kms(format!("Failed to create AWS signer: {}", e)))?;
 
-        println!("✅ KMS signer initialized successfully");
-        println!(
+        info!("✅ KMS signer initialized successfully");
+        info!(
             "📍 Ethereum address: 0x{}",
             hex::encode(signer.address().as_slice())
         );
@@ -35,6 +37,15 @@ impl KmsSigner {
         self.signer.address()
     }
 
+    // Round-trips to KMS, so a revoked key or missing permissions show up here
+    pub async fn check_available(&self) -> Result<()> {
+        self.signer
+            .get_pubkey()
+            .await
+            .map_err(|e| KeeperError::Kms(format!("KMS key unavailable: {}", e)))?;
+        Ok(())
+    }
+
     pub fn as_alloy_signer(&self) -> &AwsSigner {
         &self.signer
     }
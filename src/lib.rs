@@ -1,10 +1,28 @@
+pub mod block_waiter;
 pub mod blockchain;
 pub mod config;
 pub mod contracts;
+pub mod daemon;
+pub mod error;
+pub mod fees;
+pub mod gas_ceiling;
 pub mod jobs;
+pub mod journal;
 pub mod kms_signer;
+pub mod logging;
+pub mod metrics;
+pub mod nonce_manager;
+pub mod notifier;
+pub mod private_relay;
+pub mod profitability;
+pub mod quorum;
+pub mod report;
 pub mod retry;
+pub mod rpc_pool;
+pub mod run_lock;
+pub mod server;
 pub mod sources;
+pub mod subscription_provider;
 pub mod transaction_monitor;
 
 pub use blockchain::BlockchainClient;
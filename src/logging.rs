@@ -0,0 +1,45 @@
+use clap::ValueEnum;
+use std::io::IsTerminal;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::EnvFilter;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    // Human readable, for local runs
+    #[default]
+    Pretty,
+    // One JSON object per line with the active spans, for the log pipeline
+    Json,
+}
+
+// Installs the global subscriber. Verbosity follows RUST_LOG (default: info).
+// With `stderr` set, logs stay off stdout so it can carry machine-readable output.
+pub fn init(format: LogFormat, stderr: bool) {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let (writer, ansi) = if stderr {
+        (
+            BoxMakeWriter::new(std::io::stderr),
+            std::io::stderr().is_terminal(),
+        )
+    } else {
+        (
+            BoxMakeWriter::new(std::io::stdout),
+            std::io::stdout().is_terminal(),
+        )
+    };
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_target(false)
+        .with_writer(writer)
+        .with_ansi(ansi);
+
+    // try_init so tests or embedders that already installed a subscriber keep theirs
+    let _ = match format {
+        LogFormat::Pretty => builder.try_init(),
+        LogFormat::Json => builder
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .try_init(),
+    };
+}
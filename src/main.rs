@@ -1,15 +1,48 @@
+mod block_waiter;
 mod blockchain;
 mod config;
 mod contracts;
+mod daemon;
+mod error;
+mod fees;
+mod gas_ceiling;
 mod jobs;
+mod journal;
 mod kms_signer;
+mod logging;
+mod metrics;
+mod nonce_manager;
+mod notifier;
+mod private_relay;
+mod profitability;
+mod quorum;
+mod report;
 mod retry;
+mod rpc_pool;
+mod run_lock;
+mod server;
 mod sources;
+mod subscription_provider;
 mod transaction_monitor;
 
 use anyhow::Result;
 use config::ChainConfig;
-use jobs::{BoostRewardsJob, ClaimYieldJob, DistributeRewardsJob};
+use daemon::{Daemon, ScheduleFile};
+use error::KeeperError;
+use jobs::boost_rewards_s3::BoostRewardsS3;
+use jobs::{BoostRewardsJob, ClaimYieldJob, DistributeRewardsJob, JobContext, JobOutcome};
+use logging::LogFormat;
+use metrics::metrics;
+use notifier::{Alert, AlertKind, Notifier};
+use report::{OutputFormat, RunRecorder};
+use run_lock::{RunLock, RunLockGuard};
+use server::KeeperStatus;
+use sources::s3_campaign_source::S3CampaignSource;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::process::ExitCode;
+use std::sync::Arc;
+use tracing::{info_span, warn, Instrument};
 
 use clap::{Parser, Subcommand};
 
@@ -19,6 +52,24 @@ use clap::{Parser, Subcommand};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    #[arg(long, global = true)]
+    http_addr: Option<SocketAddr>, // Serve /healthz, /readyz, /status and /metrics, e.g. 0.0.0.0:8080
+
+    #[arg(long, global = true)]
+    pushgateway_url: Option<String>, // Push metrics here when a one-shot run finishes
+
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        env = "LOG_FORMAT",
+        default_value = "pretty"
+    )]
+    log_format: LogFormat, // pretty | json; verbosity follows RUST_LOG
+
+    #[arg(long, global = true, value_enum, default_value = "text")]
+    output: OutputFormat, // json prints the run report to stdout
 }
 
 #[derive(Subcommand)]
@@ -89,6 +140,14 @@ enum Commands {
         #[arg(long)]
         s3_region: Option<String>, // AWS region for S3
     },
+    Daemon {
+        #[arg(long)]
+        schedule: String, // TOML file mapping cron expressions to jobs and configs
+        #[arg(long)]
+        kms_key_id: Option<String>,
+        #[arg(long)]
+        aws_region: Option<String>,
+    },
 }
 
 fn setup_config(
@@ -96,17 +155,18 @@ fn setup_config(
     kms_key_id: Option<String>,
     aws_region: Option<String>,
 ) -> Result<ChainConfig> {
-    let mut chain_config = ChainConfig::load(config_path)?;
+    let mut chain_config = ChainConfig::load(config_path)
+        .map_err(|e| KeeperError::Config(format!("{}: {:#}", config_path, e)))?;
 
     // Override KMS settings from CLI if provided
     if let Some(key_id) = kms_key_id {
         let region = aws_region
             .or_else(|| chain_config.kms.as_ref().and_then(|kms| kms.region.clone()))
             .ok_or_else(|| {
-                anyhow::anyhow!(
+                KeeperError::Config(format!(
                     "KMS region not specified. Use --aws-region or configure region in {}",
                     config_path
-                )
+                ))
             })?;
 
         chain_config.kms = Some(crate::config::KmsSettings {
@@ -118,11 +178,133 @@ fn setup_config(
     Ok(chain_config)
 }
 
+// Dry runs never send transactions, so they don't contend for the run lock
+async fn acquire_run_lock(
+    chain_config: &ChainConfig,
+    job: &str,
+    dry_run: bool,
+) -> Result<Option<RunLockGuard>> {
+    if dry_run {
+        return Ok(None);
+    }
+    Ok(Some(RunLock::acquire_for_job(chain_config, job).await?))
+}
+
+async fn release_run_lock(lock: Option<RunLockGuard>) {
+    if let Some(lock) = lock {
+        lock.release().await;
+    }
+}
+
+// Runs a one-shot job under the run lock with a freshly connected context, recording
+// the run for the status endpoint, metrics and the run report
+async fn run_job<F, Fut>(
+    chain_config: &ChainConfig,
+    job: &str,
+    dry_run: bool,
+    status: &KeeperStatus,
+    pushgateway_url: Option<&str>,
+    output: OutputFormat,
+    f: F,
+) -> Result<JobOutcome>
+where
+    F: FnOnce(Arc<JobContext>, RunRecorder) -> Fut,
+    Fut: Future<Output = Result<JobOutcome>>,
+{
+    let span = info_span!(
+        "job_run",
+        job,
+        chain_id = chain_config.chain.chain_id,
+        dry_run
+    );
+    // Boxed: the nested job futures are deep enough to hit the compiler's layout limit
+    Box::pin(async {
+        let lock = acquire_run_lock(chain_config, job, dry_run).await?;
+        status.run_started(job);
+        let started = std::time::Instant::now();
+        let notifier = Arc::new(Notifier::from_config(chain_config));
+        let report = RunRecorder::new(job, chain_config, dry_run);
+        // Boxed as well, or the lock guard's select pushes the layout past the limit again
+        let work = Box::pin(async {
+            let ctx = Arc::new(
+                JobContext::connect(chain_config)
+                    .await?
+                    .with_notifier(notifier.clone()),
+            );
+            status.register_context(ctx.clone());
+            if let Some(reason) = ctx.check_liveness().await? {
+                report.decision(format!("skipped: chain stalled ({})", reason));
+                return Ok(JobOutcome::ChainStalled);
+            }
+            // Dry runs never send, so they leave in-flight transactions alone
+            if !dry_run {
+                ctx.reconcile_journal().await?;
+            }
+            let result = f(ctx.clone(), report.clone()).await;
+            ctx.check_native_balance().await;
+            result
+        });
+        let result = match &lock {
+            Some(lock) => lock.guard(work).await,
+            None => work.await,
+        };
+        status.run_finished(job, &result);
+        let run_report = report.finish(&result);
+        run_report.write(chain_config).await;
+        if output == OutputFormat::Json {
+            match run_report.to_json() {
+                Ok(json) => println!("{}", json),
+                Err(e) => warn!("⚠️  Failed to serialize run report: {}", e),
+            }
+        }
+        if let Err(e) = &result {
+            notifier
+                .notify(Alert::new(
+                    AlertKind::JobFailed,
+                    chain_config.chain.chain_id,
+                    job,
+                    format!("{} failed: {}", job, e),
+                ))
+                .await;
+        }
+        metrics().record_job_run(job, result.is_ok(), started.elapsed());
+        release_run_lock(lock).await;
+
+        if let Some(url) = pushgateway_url {
+            if let Err(e) = metrics().push(url, job).await {
+                warn!("⚠️  {}", e);
+            }
+        }
+        result
+    })
+    .instrument(span)
+    .await
+}
+
+// Exit codes are documented in the README and defined in error.rs
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> ExitCode {
     let cli = Cli::parse();
+    logging::init(cli.log_format, cli.output == OutputFormat::Json);
 
-    match cli.command {
+    match run(cli).await {
+        Ok(outcome) => ExitCode::from(outcome.exit_code()),
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            ExitCode::from(error::exit_code(&e))
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<JobOutcome> {
+    let status = KeeperStatus::new();
+    let pushgateway = cli.pushgateway_url.as_deref();
+    let server = match cli.http_addr {
+        Some(addr) => Some(server::spawn(addr, status.clone()).await?),
+        None => None,
+    };
+
+    let outcome = match cli.command {
         Commands::ClaimYield {
             config,
             kms_key_id,
@@ -130,8 +312,17 @@ async fn main() -> Result<()> {
             dry_run,
         } => {
             let chain_config = setup_config(&config, kms_key_id, aws_region)?;
-            let job = ClaimYieldJob::new(chain_config, dry_run);
-            job.execute().await?;
+            let job = ClaimYieldJob::new(chain_config.clone(), dry_run);
+            run_job(
+                &chain_config,
+                "claim-yield",
+                dry_run,
+                &status,
+                pushgateway,
+                cli.output,
+                |ctx, report| async move { job.with_report(report).execute(&ctx).await },
+            )
+            .await?
         }
         Commands::DistributeRewards {
             config,
@@ -140,8 +331,17 @@ async fn main() -> Result<()> {
             dry_run,
         } => {
             let chain_config = setup_config(&config, kms_key_id, aws_region)?;
-            let job = DistributeRewardsJob::new(chain_config, dry_run);
-            job.execute().await?;
+            let job = DistributeRewardsJob::new(chain_config.clone(), dry_run);
+            run_job(
+                &chain_config,
+                "distribute-rewards",
+                dry_run,
+                &status,
+                pushgateway,
+                cli.output,
+                |ctx, report| async move { job.with_report(report).execute(&ctx).await },
+            )
+            .await?
         }
         Commands::BoostRewardsDistribute {
             config,
@@ -156,15 +356,30 @@ async fn main() -> Result<()> {
         } => {
             let chain_config = setup_config(&config, kms_key_id, aws_region)?;
             let job = BoostRewardsJob::new(
-                chain_config,
+                chain_config.clone(),
                 token_address,
                 total_amount,
                 start_date,
                 end_date,
                 campaign_id,
                 dry_run,
-            )?;
-            job.execute().await?;
+            )
+            .map_err(|e| KeeperError::Config(e.to_string()))?;
+            run_job(
+                &chain_config,
+                "boost-rewards-distribute",
+                dry_run,
+                &status,
+                pushgateway,
+                cli.output,
+                |ctx, report| async move {
+                    job.with_report(report)
+                        .execute(&ctx)
+                        .await
+                        .map(|_| JobOutcome::Completed)
+                },
+            )
+            .await?
         }
         Commands::BoostRewardsS3 {
             config,
@@ -175,52 +390,52 @@ async fn main() -> Result<()> {
         } => {
             let chain_config = setup_config(&config, kms_key_id, aws_region)?;
 
-            // Get S3 region: CLI arg -> env var -> KMS region
-            let s3_region = s3_region
-                .or_else(|| std::env::var("S3_REGION").ok())
-                .or_else(|| std::env::var("AWS_REGION").ok())
-                .or_else(|| chain_config.kms.as_ref().and_then(|kms| kms.region.clone()))
-                .unwrap();
-
-            // Parse S3 path (supports both s3://bucket/key and bucket/key)
-            let (bucket, key) = if campaigns_s3.starts_with("s3://") {
-                let path = campaigns_s3.strip_prefix("s3://").unwrap();
-                let parts: Vec<&str> = path.splitn(2, '/').collect();
-                if parts.len() != 2 {
-                    return Err(anyhow::anyhow!("Invalid S3 path format: {}", campaigns_s3));
-                }
-                (parts[0].to_string(), parts[1].to_string())
-            } else {
-                let parts: Vec<&str> = campaigns_s3.splitn(2, '/').collect();
-                if parts.len() != 2 {
-                    return Err(anyhow::anyhow!("Invalid S3 path format: {}", campaigns_s3));
-                }
-                (parts[0].to_string(), parts[1].to_string())
-            };
-
-            // Initialize S3 client (same pattern as KMS)
-            println!("🔧 Initializing S3 client...");
-            println!("   Region: {}", s3_region);
-            println!("   Bucket: {}", bucket);
-            println!("   Key: {}", key);
-
-            let aws_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
-                .region(aws_config::Region::new(s3_region.clone()))
-                .load()
-                .await;
-            let s3_client = aws_sdk_s3::Client::new(&aws_config);
-
-            // Create S3 campaign source
-            let campaign_source = Box::new(
-                crate::sources::s3_campaign_source::S3CampaignSource::new(s3_client, bucket, key),
-            );
+            let s3_region = S3CampaignSource::resolve_region(s3_region, &chain_config)
+                .map_err(|e| KeeperError::Config(e.to_string()))?;
+            let campaign_source =
+                Box::new(S3CampaignSource::from_uri(&campaigns_s3, &s3_region).await?);
 
             // Run job
-            let job =
-                crate::jobs::boost_rewards_s3::BoostRewardsS3::new(chain_config, campaign_source);
-            job.run().await?;
+            let job = BoostRewardsS3::new(chain_config.clone(), campaign_source);
+            run_job(
+                &chain_config,
+                "boost-rewards-s3",
+                false,
+                &status,
+                pushgateway,
+                cli.output,
+                |ctx, report| async move {
+                    let summaries = job.with_report(report).run(&ctx).await?;
+                    BoostRewardsS3::outcome(&summaries)
+                },
+            )
+            .await?
+        }
+        Commands::Daemon {
+            schedule,
+            kms_key_id,
+            aws_region,
+        } => {
+            let schedule_file = ScheduleFile::load(&schedule)
+                .map_err(|e| KeeperError::Config(format!("{}: {:#}", schedule, e)))?;
+            let mut jobs = Vec::new();
+            for job in schedule_file.jobs {
+                let chain_config =
+                    setup_config(&job.config, kms_key_id.clone(), aws_region.clone())?;
+                jobs.push((job, chain_config));
+            }
+
+            Daemon::new(jobs, status.clone())
+                .map_err(|e| KeeperError::Config(e.to_string()))?
+                .run()
+                .await?;
+            JobOutcome::Completed
         }
+    };
+
+    if let Some((_, handle)) = server {
+        handle.abort();
     }
 
-    Ok(())
+    Ok(outcome)
 }
@@ -0,0 +1,240 @@
+use alloy::primitives::U256;
+use anyhow::Result;
+use prometheus::{
+    CounterVec, Encoder, GaugeVec, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry,
+    TextEncoder,
+};
+use std::sync::LazyLock;
+use std::time::Duration;
+use tracing::{info, warn};
+
+// All keeper metrics, registered once in a dedicated registry
+pub struct KeeperMetrics {
+    registry: Registry,
+    pub job_runs: IntCounterVec,
+    pub job_duration: HistogramVec,
+    pub retry_attempts: IntCounterVec,
+    pub tx_confirmation: HistogramVec,
+    pub tx_gas_used: HistogramVec,
+    pub pending_yield: GaugeVec,
+    pub snapshot_age: GaugeVec,
+    pub head_block_age: GaugeVec,
+    pub native_balance: GaugeVec,
+    pub token_balance: GaugeVec,
+    pub campaign_paid: CounterVec,
+    pub rpc_requests: IntCounterVec,
+    pub rpc_endpoint_healthy: GaugeVec,
+    pub rpc_endpoint_block_lag: GaugeVec,
+}
+
+static METRICS: LazyLock<KeeperMetrics> = LazyLock::new(KeeperMetrics::new);
+
+pub fn metrics() -> &'static KeeperMetrics {
+    &METRICS
+}
+
+impl KeeperMetrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+
+        let job_runs = IntCounterVec::new(
+            Opts::new("keeper_job_runs_total", "Job runs by job and outcome"),
+            &["job", "outcome"],
+        )
+        .expect("valid metric");
+        let job_duration = HistogramVec::new(
+            HistogramOpts::new("keeper_job_duration_seconds", "Job run duration").buckets(vec![
+                1.0, 5.0, 15.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1800.0,
+            ]),
+            &["job", "outcome"],
+        )
+        .expect("valid metric");
+        let retry_attempts = IntCounterVec::new(
+            Opts::new(
+                "keeper_retry_attempts_total",
+                "Attempts made by execute_with_retry by operation and result",
+            ),
+            &["operation", "result"],
+        )
+        .expect("valid metric");
+        let tx_confirmation = HistogramVec::new(
+            HistogramOpts::new(
+                "keeper_tx_confirmation_seconds",
+                "Time from monitoring start to receipt or timeout",
+            )
+            .buckets(vec![1.0, 2.0, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0, 300.0]),
+            &["status"],
+        )
+        .expect("valid metric");
+        let tx_gas_used = HistogramVec::new(
+            HistogramOpts::new("keeper_tx_gas_used", "Gas used by confirmed transactions").buckets(
+                prometheus::exponential_buckets(21_000.0, 2.0, 10).expect("valid buckets"),
+            ),
+            &["status"],
+        )
+        .expect("valid metric");
+        let pending_yield = GaugeVec::new(
+            Opts::new(
+                "keeper_pending_yield_wei",
+                "Last observed USDSC pending yield",
+            ),
+            &["chain_id"],
+        )
+        .expect("valid metric");
+        let snapshot_age = GaugeVec::new(
+            Opts::new(
+                "keeper_snapshot_age_seconds",
+                "Age of the last RewardRedistributor TVL snapshot",
+            ),
+            &["chain_id"],
+        )
+        .expect("valid metric");
+        let head_block_age = GaugeVec::new(
+            Opts::new(
+                "keeper_head_block_age_seconds",
+                "Age of the latest block at the last liveness check",
+            ),
+            &["chain_id"],
+        )
+        .expect("valid metric");
+        let native_balance = GaugeVec::new(
+            Opts::new("keeper_native_balance_wei", "Keeper native gas balance"),
+            &["chain_id"],
+        )
+        .expect("valid metric");
+        let token_balance = GaugeVec::new(
+            Opts::new(
+                "keeper_token_balance",
+                "Keeper token balance in token units",
+            ),
+            &["chain_id", "token"],
+        )
+        .expect("valid metric");
+        let campaign_paid = CounterVec::new(
+            Opts::new(
+                "keeper_campaign_paid_total",
+                "Boost campaign amounts paid in token units",
+            ),
+            &["chain_id", "campaign_id", "token"],
+        )
+        .expect("valid metric");
+        let rpc_requests = IntCounterVec::new(
+            Opts::new(
+                "keeper_rpc_requests_total",
+                "RPC requests by endpoint and result (ok, error, rate_limited)",
+            ),
+            &["chain_id", "endpoint", "result"],
+        )
+        .expect("valid metric");
+        let rpc_endpoint_healthy = GaugeVec::new(
+            Opts::new(
+                "keeper_rpc_endpoint_healthy",
+                "1 if the RPC endpoint is in rotation, 0 if cooling down or lagging",
+            ),
+            &["chain_id", "endpoint"],
+        )
+        .expect("valid metric");
+        let rpc_endpoint_block_lag = GaugeVec::new(
+            Opts::new(
+                "keeper_rpc_endpoint_block_lag",
+                "Blocks behind the best RPC endpoint at the last health check",
+            ),
+            &["chain_id", "endpoint"],
+        )
+        .expect("valid metric");
+
+        for collector in [
+            Box::new(job_runs.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(job_duration.clone()),
+            Box::new(retry_attempts.clone()),
+            Box::new(tx_confirmation.clone()),
+            Box::new(tx_gas_used.clone()),
+            Box::new(pending_yield.clone()),
+            Box::new(snapshot_age.clone()),
+            Box::new(head_block_age.clone()),
+            Box::new(native_balance.clone()),
+            Box::new(token_balance.clone()),
+            Box::new(campaign_paid.clone()),
+            Box::new(rpc_requests.clone()),
+            Box::new(rpc_endpoint_healthy.clone()),
+            Box::new(rpc_endpoint_block_lag.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("metric registered once");
+        }
+
+        Self {
+            registry,
+            job_runs,
+            job_duration,
+            retry_attempts,
+            tx_confirmation,
+            tx_gas_used,
+            pending_yield,
+            snapshot_age,
+            head_block_age,
+            native_balance,
+            token_balance,
+            campaign_paid,
+            rpc_requests,
+            rpc_endpoint_healthy,
+            rpc_endpoint_block_lag,
+        }
+    }
+
+    pub fn record_job_run(&self, job: &str, success: bool, duration: Duration) {
+        let outcome = if success { "success" } else { "failure" };
+        self.job_runs.with_label_values(&[job, outcome]).inc();
+        self.job_duration
+            .with_label_values(&[job, outcome])
+            .observe(duration.as_secs_f64());
+    }
+
+    pub fn record_pending_yield(&self, chain_id: u64, pending_yield: U256) {
+        self.pending_yield
+            .with_label_values(&[&chain_id.to_string()])
+            .set(u256_to_f64(pending_yield));
+    }
+
+    pub fn record_native_balance(&self, chain_id: u64, balance: U256) {
+        self.native_balance
+            .with_label_values(&[&chain_id.to_string()])
+            .set(u256_to_f64(balance));
+    }
+
+    // Prometheus text exposition of every keeper metric
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            warn!("⚠️  Failed to encode metrics: {}", e);
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+
+    // One-shot cron runs exit before anyone scrapes them, so they push instead
+    pub async fn push(&self, gateway_url: &str, job: &str) -> Result<()> {
+        let url = format!("{}/metrics/job/{}", gateway_url.trim_end_matches('/'), job);
+        let response = reqwest::Client::new()
+            .put(&url)
+            .header("Content-Type", TextEncoder::new().format_type())
+            .body(self.render())
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to push metrics to {}: {}", url, e))?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Pushgateway {} returned {}",
+                url,
+                response.status()
+            ));
+        }
+        info!("📈 Pushed metrics to {}", url);
+        Ok(())
+    }
+}
+
+// Gauges are f64; wei values beyond 2^53 lose precision, which is fine for dashboards
+pub fn u256_to_f64(value: U256) -> f64 {
+    value.to_string().parse().unwrap_or(f64::MAX)
+}
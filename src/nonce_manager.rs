@@ -0,0 +1,92 @@
+use alloy::network::Ethereum;
+use alloy::primitives::Address;
+use alloy::providers::Provider;
+use anyhow::Result;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+// Hands out consecutive nonces for the keeper address so several transactions can be
+// in flight at once without racing on `eth_getTransactionCount`.
+pub struct NonceManager {
+    provider: Arc<dyn Provider<Ethereum>>,
+    address: Address,
+    state: Mutex<NonceState>,
+}
+
+#[derive(Default)]
+struct NonceState {
+    // Next nonce never handed out; read from the node once, on first use
+    next: Option<u64>,
+    // Given back before reaching the node; handed out again first, lowest first
+    released: BTreeSet<u64>,
+}
+
+impl NonceManager {
+    pub fn new(provider: Arc<dyn Provider<Ethereum>>, address: Address) -> Self {
+        Self {
+            provider,
+            address,
+            state: Mutex::new(NonceState::default()),
+        }
+    }
+
+    pub async fn next_nonce(&self) -> Result<u64> {
+        Ok(self.next_nonces(1).await?[0])
+    }
+
+    // `count` nonces in increasing order under one lock, so a batch signed together
+    // stays ordered even while other jobs allocate concurrently. Released nonces come
+    // first so their gaps get filled.
+    pub async fn next_nonces(&self, count: usize) -> Result<Vec<u64>> {
+        let mut state = self.state.lock().await;
+        let mut next = match state.next {
+            Some(nonce) => nonce,
+            None => {
+                // Pending count includes our own transactions still in the mempool
+                self.provider
+                    .get_transaction_count(self.address)
+                    .pending()
+                    .await?
+            }
+        };
+        let mut nonces = Vec::with_capacity(count);
+        while nonces.len() < count {
+            match state.released.pop_first() {
+                Some(nonce) => nonces.push(nonce),
+                None => {
+                    nonces.push(next);
+                    next += 1;
+                }
+            }
+        }
+        state.next = Some(next);
+        Ok(nonces)
+    }
+
+    // Give back a nonce whose transaction never reached the node. The node is never
+    // asked again while other allocations may still be unsent, since its pending count
+    // would hand out their nonces a second time. Returns false when later nonces are
+    // already out: until something is sent at this one, they can't be mined.
+    pub async fn release(&self, nonce: u64) -> bool {
+        let mut state = self.state.lock().await;
+        if state.next == Some(nonce + 1) {
+            // The latest allocation: shrink back, along with released ones below it
+            let mut next = nonce;
+            while next > 0 && state.released.remove(&(next - 1)) {
+                next -= 1;
+            }
+            state.next = Some(next);
+            true
+        } else {
+            state.released.insert(nonce);
+            false
+        }
+    }
+
+    // Takes a released nonce back out so the caller can fill its gap itself. False if
+    // another allocation reused it in the meantime.
+    pub async fn claim_released(&self, nonce: u64) -> bool {
+        self.state.lock().await.released.remove(&nonce)
+    }
+}
@@ -0,0 +1,240 @@
+use crate::config::{AlertSettings, ChainConfig, WebhookFormat, WebhookSettings};
+use crate::run_lock::RunLock;
+use anyhow::Result;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::{info, warn};
+
+const RATE_LIMIT_WINDOW: ChronoDuration = ChronoDuration::hours(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertKind {
+    JobFailed,
+    TransactionFailed,
+    TransactionTimeout,
+    LowNativeBalance,
+    LowTokenBalance,
+    SnapshotTooOld,
+    ChainStalled,
+}
+
+impl AlertKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertKind::JobFailed => "job_failed",
+            AlertKind::TransactionFailed => "transaction_failed",
+            AlertKind::TransactionTimeout => "transaction_timeout",
+            AlertKind::LowNativeBalance => "low_native_balance",
+            AlertKind::LowTokenBalance => "low_token_balance",
+            AlertKind::SnapshotTooOld => "snapshot_too_old",
+            AlertKind::ChainStalled => "chain_stalled",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Alert {
+    pub kind: AlertKind,
+    pub chain_id: u64,
+    // What the alert is about (job name, tx hash, token...); alerts with the same kind,
+    // chain and subject are duplicates
+    pub subject: String,
+    pub message: String,
+}
+
+impl Alert {
+    pub fn new(
+        kind: AlertKind,
+        chain_id: u64,
+        subject: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind,
+            chain_id,
+            subject: subject.into(),
+            message: message.into(),
+        }
+    }
+
+    fn dedup_key(&self) -> String {
+        format!("{}:{}:{}", self.kind.as_str(), self.chain_id, self.subject)
+    }
+
+    fn title(&self) -> String {
+        format!(
+            "🚨 Keeper alert on chain {}: {}",
+            self.chain_id,
+            self.kind.as_str()
+        )
+    }
+
+    pub fn payload(&self, format: WebhookFormat) -> Value {
+        match format {
+            WebhookFormat::Generic => json!({
+                "kind": self.kind.as_str(),
+                "chain_id": self.chain_id,
+                "subject": self.subject,
+                "message": self.message,
+                "timestamp": Utc::now().to_rfc3339(),
+            }),
+            WebhookFormat::Slack => json!({
+                "text": format!("*{}*\n{}", self.title(), self.message),
+            }),
+            WebhookFormat::Discord => json!({
+                "content": format!("**{}**\n{}", self.title(), self.message),
+            }),
+        }
+    }
+}
+
+// When each dedup key was last sent, and every send within the rate limit window
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct NotifierState {
+    last_sent: HashMap<String, DateTime<Utc>>,
+    recent: VecDeque<DateTime<Utc>>,
+}
+
+// Sends alerts to the configured webhooks. Delivery is best effort: failures are logged
+// and never fail the job that raised the alert.
+pub struct Notifier {
+    settings: AlertSettings,
+    http: reqwest::Client,
+    state: Mutex<NotifierState>,
+    // Dedup and rate limit state kept on disk, so one-shot runs share it
+    state_path: Option<PathBuf>,
+}
+
+impl Notifier {
+    pub fn new(settings: AlertSettings) -> Self {
+        Self {
+            settings,
+            http: reqwest::Client::new(),
+            state: Mutex::new(NotifierState::default()),
+            state_path: None,
+        }
+    }
+
+    // Keeps its state next to the transaction journal, like gas ceiling deferrals
+    pub fn from_config(config: &ChainConfig) -> Self {
+        Self::new(config.alerts.clone()).with_state_file(
+            config
+                .journal
+                .dir()
+                .join(format!("{}-alerts.json", RunLock::lock_name(config))),
+        )
+    }
+
+    pub fn with_state_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.state_path = Some(path.into());
+        self
+    }
+
+    pub fn settings(&self) -> &AlertSettings {
+        &self.settings
+    }
+
+    // Returns whether the alert was sent (false when disabled, duplicate or rate limited)
+    pub async fn notify(&self, alert: Alert) -> bool {
+        warn!("🚨 {}: {}", alert.kind.as_str(), alert.message);
+        if self.settings.webhooks.is_empty() || !self.admit(&alert) {
+            return false;
+        }
+
+        for sink in &self.settings.webhooks {
+            self.deliver(sink, &alert).await;
+        }
+        true
+    }
+
+    // Dedup and rate limit bookkeeping; an admitted alert counts as sent
+    fn admit(&self, alert: &Alert) -> bool {
+        let Ok(mut state) = self.state.lock() else {
+            return false;
+        };
+        // Re-read on every alert: other runs may have sent since
+        if let Some(stored) = self.load_state() {
+            *state = stored;
+        }
+        let now = Utc::now();
+        let key = alert.dedup_key();
+        let dedup_window = ChronoDuration::seconds(self.settings.dedup_window_seconds as i64);
+
+        if let Some(last) = state.last_sent.get(&key) {
+            if now - *last < dedup_window {
+                info!("🔕 Suppressed duplicate alert {}", key);
+                return false;
+            }
+        }
+
+        while let Some(oldest) = state.recent.front() {
+            if now - *oldest < RATE_LIMIT_WINDOW {
+                break;
+            }
+            state.recent.pop_front();
+        }
+        if state.recent.len() >= self.settings.max_alerts_per_hour as usize {
+            warn!("🔕 Alert rate limit reached, dropping {}", key);
+            return false;
+        }
+
+        state.recent.push_back(now);
+        state.last_sent.insert(key, now);
+        state.last_sent.retain(|_, last| now - *last < dedup_window);
+        if let Err(e) = self.save_state(&state) {
+            warn!("⚠️  Failed to persist alert state: {}", e);
+        }
+        true
+    }
+
+    // None without a state file, or when it can't be read (then memory state is used)
+    fn load_state(&self) -> Option<NotifierState> {
+        let path = self.state_path.as_ref()?;
+        match fs::read_to_string(path) {
+            Ok(content) => Some(serde_json::from_str(&content).unwrap_or_default()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                warn!("⚠️  Failed to read alert state {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    fn save_state(&self, state: &NotifierState) -> Result<()> {
+        let Some(path) = &self.state_path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(state)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    async fn deliver(&self, sink: &WebhookSettings, alert: &Alert) {
+        let result = self
+            .http
+            .post(&sink.url)
+            .json(&alert.payload(sink.format))
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await;
+        match result {
+            Ok(response) if response.status().is_success() => {}
+            Ok(response) => warn!(
+                "⚠️  Alert webhook returned {} for {}",
+                response.status(),
+                alert.kind.as_str()
+            ),
+            Err(e) => warn!("⚠️  Failed to deliver alert {}: {}", alert.kind.as_str(), e),
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use crate::block_waiter::BlockWaiter;
+use crate::blockchain::BlockchainClient;
+use crate::config::{ChainConfig, PrivateRelaySettings, RelayMethod};
+use crate::error::KeeperError;
+use crate::rpc_pool::endpoint_label;
+use alloy::hex;
+use alloy::network::Ethereum;
+use alloy::primitives::{keccak256, Bytes, B256};
+use alloy::providers::Provider;
+use alloy::signers::local::PrivateKeySigner;
+use alloy::signers::Signer;
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+use tracing::{info, warn};
+use url::Url;
+
+// Relay statuses after which the transaction won't be included privately
+const TERMINAL_STATUSES: [&str; 2] = ["FAILED", "CANCELLED"];
+
+struct PendingSubmission {
+    raw_tx: Bytes,
+    // Head when the relay first got the transaction
+    submitted_at_block: u64,
+}
+
+// Flashbots-style relay for jobs that shouldn't go through the public mempool. Sent
+// transactions stay pending here until they are mined or fall back to the public
+// mempool as the same signed bytes, so the fallback can never double-send.
+pub struct PrivateRelay {
+    settings: PrivateRelaySettings,
+    label: String,
+    auth: PrivateKeySigner,
+    http: reqwest::Client,
+    pending: Mutex<HashMap<B256, PendingSubmission>>,
+}
+
+impl PrivateRelay {
+    // None when there is no `[private_relay]` section
+    pub fn from_config(config: &ChainConfig) -> Result<Option<Self>> {
+        let Some(settings) = config.private_relay.clone() else {
+            return Ok(None);
+        };
+        let url = Url::parse(&settings.url)
+            .map_err(|e| KeeperError::Config(format!("Invalid private relay URL: {}", e)))?;
+        let auth_key = settings
+            .auth_key
+            .as_deref()
+            .map(str::trim)
+            .filter(|key| !key.is_empty() && !key.contains("${"));
+        let auth = match auth_key {
+            Some(key) => key.parse::<PrivateKeySigner>().map_err(|e| {
+                KeeperError::Config(format!("Invalid private relay auth_key: {}", e))
+            })?,
+            None => PrivateKeySigner::random(),
+        };
+        Ok(Some(Self {
+            label: endpoint_label(&url, 0),
+            settings,
+            auth,
+            http: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()?,
+            pending: Mutex::new(HashMap::new()),
+        }))
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn enabled_for(&self, job: &str) -> bool {
+        self.settings.jobs.iter().any(|enabled| enabled == job)
+    }
+
+    fn pending(&self) -> MutexGuard<'_, HashMap<B256, PendingSubmission>> {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // Hands a signed transaction to the relay instead of the public mempool
+    pub async fn submit(
+        &self,
+        provider: &dyn Provider<Ethereum>,
+        tx_hash: B256,
+        raw_tx: &[u8],
+    ) -> Result<()> {
+        let block = provider
+            .get_block_number()
+            .await
+            .map_err(|e| KeeperError::Rpc(e.to_string()))?;
+        self.send(raw_tx, block).await?;
+        info!(
+            "🕶️  Sent {:?} privately to {} ({:?}, head {})",
+            tx_hash, self.label, self.settings.method, block
+        );
+        self.pending().insert(
+            tx_hash,
+            PendingSubmission {
+                raw_tx: Bytes::copy_from_slice(raw_tx),
+                submitted_at_block: block,
+            },
+        );
+        Ok(())
+    }
+
+    async fn send(&self, raw_tx: &[u8], block: u64) -> Result<()> {
+        let raw_tx = hex::encode_prefixed(raw_tx);
+        let (method, params) = match self.settings.method {
+            RelayMethod::PrivateTransaction => (
+                "eth_sendPrivateTransaction",
+                json!([{
+                    "tx": raw_tx,
+                    "maxBlockNumber": format!("{:#x}", block + self.settings.max_blocks),
+                }]),
+            ),
+            RelayMethod::Bundle => (
+                "eth_sendBundle",
+                json!([{ "txs": [raw_tx], "blockNumber": format!("{:#x}", block + 1) }]),
+            ),
+        };
+        self.call(method, params).await?;
+        Ok(())
+    }
+
+    // JSON-RPC call signed the Flashbots way: the EIP-191 signature of the body's
+    // keccak hash, sent as `address:signature`
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let body =
+            json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }).to_string();
+        let signature = self
+            .auth
+            .sign_message(keccak256(&body).to_string().as_bytes())
+            .await?;
+        let response = self
+            .http
+            .post(&self.settings.url)
+            .header("Content-Type", "application/json")
+            .header(
+                "X-Flashbots-Signature",
+                format!(
+                    "{}:{}",
+                    self.auth.address(),
+                    hex::encode_prefixed(signature.as_bytes())
+                ),
+            )
+            .body(body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| KeeperError::Rpc(format!("{} {}: {}", self.label, method, e)))?;
+        let response: Value = response
+            .json()
+            .await
+            .map_err(|e| KeeperError::Rpc(format!("{} {}: {}", self.label, method, e)))?;
+        if let Some(error) = response.get("error") {
+            return Err(
+                KeeperError::Rpc(format!("{} rejected {}: {}", self.label, method, error)).into(),
+            );
+        }
+        Ok(response.get("result").cloned().unwrap_or(Value::Null))
+    }
+
+    // Waits block by block until the transaction is mined, the relay reports it failed,
+    // or `max_blocks` pass without inclusion; then broadcasts it publicly and lets the
+    // receipt wait take over. Hashes that weren't sent privately return at once.
+    pub async fn wait_for_inclusion(
+        &self,
+        client: &BlockchainClient,
+        block_waiter: &BlockWaiter,
+        tx_hash: B256,
+    ) -> Result<()> {
+        let Some((raw_tx, submitted_at_block)) = self
+            .pending()
+            .get(&tx_hash)
+            .map(|pending| (pending.raw_tx.clone(), pending.submitted_at_block))
+        else {
+            return Ok(());
+        };
+        let deadline = submitted_at_block + self.settings.max_blocks;
+        let mut block = submitted_at_block;
+
+        let reason = loop {
+            block = block_waiter.wait_for_block_after(client, block).await?;
+            let receipt = client.provider().get_transaction_receipt(tx_hash).await;
+            if matches!(receipt, Ok(Some(_))) {
+                info!("✅ Private transaction {:?} included", tx_hash);
+                self.pending().remove(&tx_hash);
+                return Ok(());
+            }
+            if let Some(status) = self.relay_status(tx_hash).await {
+                if TERMINAL_STATUSES.contains(&status.as_str()) {
+                    break format!("reported {} by {}", status, self.label);
+                }
+            }
+            if block >= deadline {
+                break format!("not included within {} blocks", self.settings.max_blocks);
+            }
+            // A bundle only targets one block, so it is sent again for the next one
+            if self.settings.method == RelayMethod::Bundle {
+                if let Err(e) = self.send(&raw_tx, block).await {
+                    warn!("⚠️  Bundle resubmission for {:?} failed: {}", tx_hash, e);
+                }
+            }
+        };
+
+        self.pending().remove(&tx_hash);
+        warn!(
+            "⚠️  Private transaction {:?} {}, sending to the public mempool",
+            tx_hash, reason
+        );
+        if let Err(e) = client.provider().send_raw_transaction(&raw_tx).await {
+            // e.g. already known or mined meanwhile; the receipt wait settles it
+            warn!("⚠️  Public fallback for {:?} failed: {}", tx_hash, e);
+        }
+        Ok(())
+    }
+
+    // Flashbots Protect style status, upper-cased; None without a status URL or when
+    // the relay doesn't answer
+    async fn relay_status(&self, tx_hash: B256) -> Option<String> {
+        let base = self.settings.status_url.as_deref()?.trim_end_matches('/');
+        let response: Value = self
+            .http
+            .get(format!("{}/{:?}", base, tx_hash))
+            .send()
+            .await
+            .ok()?
+            .json()
+            .await
+            .ok()?;
+        response
+            .get("status")?
+            .as_str()
+            .map(|status| status.to_uppercase())
+    }
+}
@@ -0,0 +1,164 @@
+use crate::blockchain::BlockchainClient;
+use crate::config::{ChainConfig, ProfitabilitySettings};
+use crate::contracts::erc20::ERC20Contract;
+use crate::error::KeeperError;
+use crate::fees::{wei_to_gwei, FeeStrategy};
+use alloy::primitives::{Address, U256};
+use alloy::rpc::types::TransactionRequest;
+use anyhow::Result;
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::{info, warn};
+
+const WEI_PER_NATIVE: f64 = 1e18;
+
+// What a claim or distribute would cost and the pending yield it takes to be worth it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProfitCheck {
+    pub gas: u64,
+    pub cost_wei: U256,
+    pub cost_usdsc: U256,
+    pub required_yield: U256,
+}
+
+pub fn cost_in_usdsc(cost_wei: U256, usdsc_per_native: f64, usdsc_decimals: u8) -> U256 {
+    let native = f64::from(cost_wei) / WEI_PER_NATIVE;
+    U256::from((native * usdsc_per_native * 10f64.powi(usdsc_decimals as i32)).ceil() as u128)
+}
+
+pub fn required_yield(cost_usdsc: U256, min_multiple: f64) -> U256 {
+    U256::from((f64::from(cost_usdsc) * min_multiple).ceil() as u128)
+}
+
+pub struct ProfitabilityGate {
+    settings: ProfitabilitySettings,
+    usdsc_address: Address,
+    fee_strategy: FeeStrategy,
+    http: reqwest::Client,
+}
+
+impl ProfitabilityGate {
+    // None when there is no `[profitability]` section
+    pub fn from_config(config: &ChainConfig) -> Result<Option<Self>> {
+        let Some(settings) = config.profitability.clone() else {
+            return Ok(None);
+        };
+        if settings.fixed_rate.is_none() && settings.price_url.is_none() {
+            return Err(KeeperError::Config(
+                "profitability needs a fixed_rate or a price_url".to_string(),
+            )
+            .into());
+        }
+        Ok(Some(Self {
+            settings,
+            usdsc_address: Address::from_str(&config.contracts.usdsc_address)?,
+            fee_strategy: FeeStrategy::from_config(config),
+            http: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()?,
+        }))
+    }
+
+    pub fn min_multiple(&self) -> f64 {
+        self.settings.min_multiple
+    }
+
+    // Native token price in USDSC: the price source first, the fixed rate as fallback
+    pub async fn usdsc_per_native(&self) -> Result<f64> {
+        let Some(url) = &self.settings.price_url else {
+            return self.fixed_rate();
+        };
+        match self.fetch_price(url).await {
+            Ok(price) => Ok(price),
+            Err(e) if self.settings.fixed_rate.is_some() => {
+                warn!("⚠️  Price source failed ({}), using the fixed rate", e);
+                self.fixed_rate()
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn fixed_rate(&self) -> Result<f64> {
+        self.settings
+            .fixed_rate
+            .ok_or_else(|| KeeperError::Config("No profitability fixed_rate".to_string()).into())
+    }
+
+    async fn fetch_price(&self, url: &str) -> Result<f64> {
+        let body: serde_json::Value = self
+            .http
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let value = body
+            .pointer(&self.settings.price_pointer)
+            .ok_or_else(|| anyhow::anyhow!("No price at {:?}", self.settings.price_pointer))?;
+        // Some sources quote prices as strings
+        let price = match value {
+            serde_json::Value::String(s) => s.parse::<f64>()?,
+            other => other
+                .as_f64()
+                .ok_or_else(|| anyhow::anyhow!("Price is not a number: {}", other))?,
+        };
+        if !price.is_finite() || price <= 0.0 {
+            return Err(anyhow::anyhow!("Invalid price {}", price));
+        }
+        Ok(price)
+    }
+
+    // Estimated gas of `txs` at current fees (base fee + tip, or the legacy gas price),
+    // plus the L1 data fee on OP-stack chains, converted to USDSC
+    pub async fn check(
+        &self,
+        client: &BlockchainClient,
+        txs: Vec<TransactionRequest>,
+    ) -> Result<ProfitCheck> {
+        let provider = client.provider();
+        let mut gas = 0u64;
+        let mut l1_fee = U256::ZERO;
+        for tx in txs {
+            let tx = tx.from(client.keeper_address());
+            l1_fee += client.estimate_l1_fee(&tx).await?.unwrap_or_default();
+            gas += match provider.estimate_gas(tx).await {
+                Ok(estimate) => estimate,
+                Err(e) => {
+                    info!(
+                        "⛽ Gas estimation failed ({}), assuming {} gas",
+                        e, self.settings.fallback_gas
+                    );
+                    self.settings.fallback_gas
+                }
+            };
+        }
+
+        let quote = self.fee_strategy.estimate(provider.as_ref()).await?;
+        let gas_price = quote.expected_gas_price();
+        let cost_wei = U256::from(gas) * U256::from(gas_price) + l1_fee;
+
+        let usdsc_decimals =
+            ERC20Contract::new(self.usdsc_address, provider.clone(), client.clone())
+                .decimals()
+                .await?;
+        let rate = self.usdsc_per_native().await?;
+        let cost_usdsc = cost_in_usdsc(cost_wei, rate, usdsc_decimals);
+        let required_yield = required_yield(cost_usdsc, self.settings.min_multiple);
+        info!(
+            "💸 Estimated cost: {} gas at {} gwei + {} wei L1 data = {} wei ({} USDSC wei at {} per native token)",
+            gas,
+            wei_to_gwei(gas_price),
+            l1_fee,
+            cost_wei,
+            cost_usdsc,
+            rate
+        );
+        Ok(ProfitCheck {
+            gas,
+            cost_wei,
+            cost_usdsc,
+            required_yield,
+        })
+    }
+}
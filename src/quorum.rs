@@ -0,0 +1,179 @@
+use crate::blockchain::BlockchainClient;
+use crate::config::ChainConfig;
+use crate::error::KeeperError;
+use crate::rpc_pool::endpoint_label;
+use alloy::eips::BlockId;
+use alloy::network::Ethereum;
+use alloy::primitives::Bytes;
+use alloy::providers::{Provider, ProviderBuilder};
+use alloy::rpc::client::RpcClient;
+use alloy::rpc::types::TransactionRequest;
+use alloy::transports::http::Http;
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+use url::Url;
+
+// Reads safety-critical values from several independent endpoints at the same block
+// and only returns a value they all agree on, so one faulty or malicious provider
+// can't make the keeper act on a wrong pending yield, snapshot or balance.
+pub struct QuorumReader {
+    endpoints: Vec<(String, Arc<dyn Provider<Ethereum>>)>,
+}
+
+impl QuorumReader {
+    pub fn new(endpoints: Vec<(String, Arc<dyn Provider<Ethereum>>)>) -> Self {
+        Self { endpoints }
+    }
+
+    // None when quorum reads are off (`quorum.size` below 2)
+    pub fn from_config(config: &ChainConfig) -> Result<Option<Self>> {
+        let size = config.quorum.size;
+        if size < 2 {
+            return Ok(None);
+        }
+        let urls: Vec<String> = if config.quorum.urls.is_empty() {
+            config
+                .chain
+                .endpoints()
+                .into_iter()
+                .map(|endpoint| endpoint.url)
+                .collect()
+        } else {
+            config.quorum.urls.clone()
+        };
+        let urls: Vec<&String> = urls.iter().filter(|url| !url.contains("${")).collect();
+        if urls.len() < size {
+            return Err(KeeperError::Config(format!(
+                "Quorum size {} needs at least {} RPC endpoints, {} configured",
+                size,
+                size,
+                urls.len()
+            ))
+            .into());
+        }
+
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.chain.rpc_timeout_seconds))
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to build HTTP client: {}", e))?;
+        let mut endpoints = Vec::new();
+        for (index, url) in urls.into_iter().take(size).enumerate() {
+            let url = Url::parse(url).map_err(|e| {
+                KeeperError::Config(format!("Invalid quorum RPC URL #{}: {}", index, e))
+            })?;
+            let label = endpoint_label(&url, index);
+            let client = RpcClient::new(Http::with_client(http.clone(), url), false);
+            let provider: Arc<dyn Provider<Ethereum>> =
+                Arc::new(ProviderBuilder::new().connect_client(client));
+            endpoints.push((label, provider));
+        }
+        info!(
+            "🗳️  Quorum reads across {} endpoints: {}",
+            endpoints.len(),
+            endpoints
+                .iter()
+                .map(|(label, _)| label.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        Ok(Some(Self::new(endpoints)))
+    }
+
+    // eth_call on every endpoint at the lowest head among them (so all of them have
+    // the block). Any endpoint failing is an RPC error; different answers abort.
+    pub async fn call(&self, label: &str, tx: TransactionRequest) -> Result<Bytes> {
+        let heads = futures::future::join_all(
+            self.endpoints
+                .iter()
+                .map(|(_, provider)| provider.get_block_number()),
+        )
+        .await;
+        let mut block = u64::MAX;
+        for ((endpoint, _), head) in self.endpoints.iter().zip(heads) {
+            let head = head.map_err(|e| {
+                KeeperError::Rpc(format!("Quorum endpoint {} unavailable: {}", endpoint, e))
+            })?;
+            block = block.min(head);
+        }
+        self.call_at(label, tx, block).await
+    }
+
+    // Same, at a block the caller already picked
+    pub async fn call_at(&self, label: &str, tx: TransactionRequest, block: u64) -> Result<Bytes> {
+        let results = futures::future::join_all(self.endpoints.iter().map(|(_, provider)| {
+            let tx = tx.clone();
+            async move { provider.call(tx).block(BlockId::number(block)).await }
+        }))
+        .await;
+        let mut answers = Vec::with_capacity(results.len());
+        for ((endpoint, _), result) in self.endpoints.iter().zip(results) {
+            let bytes = result.map_err(|e| {
+                KeeperError::Rpc(format!(
+                    "Quorum read {} failed on {} at block {}: {}",
+                    label, endpoint, block, e
+                ))
+            })?;
+            answers.push((endpoint.as_str(), bytes));
+        }
+
+        let (_, agreed) = &answers[0];
+        if answers.iter().any(|(_, bytes)| bytes != agreed) {
+            let detail = answers
+                .iter()
+                .map(|(endpoint, bytes)| format!("{}={}", endpoint, bytes))
+                .collect::<Vec<_>>()
+                .join(", ");
+            error!(
+                "🚨 Quorum disagreement on {} at block {}: {}",
+                label, block, detail
+            );
+            return Err(KeeperError::QuorumDisagreement(format!(
+                "{} at block {}: {}",
+                label, block, detail
+            ))
+            .into());
+        }
+
+        info!(
+            "🗳️  Quorum read {} agreed across {} endpoints at block {}",
+            label,
+            answers.len(),
+            block
+        );
+        Ok(agreed.clone())
+    }
+}
+
+// Critical contract reads go through the client's quorum when one is configured and
+// fall back to a plain call on the contract's provider otherwise
+pub async fn critical_call(
+    client: &BlockchainClient,
+    provider: &Arc<dyn Provider<Ethereum>>,
+    label: &str,
+    tx: TransactionRequest,
+) -> Result<Bytes> {
+    match client.quorum() {
+        Some(quorum) => quorum.call(label, tx).await,
+        None => Ok(provider.call(tx).await?),
+    }
+}
+
+// `critical_call` at a given block; None leaves the block to the quorum (its common
+// head) or the provider (latest)
+pub async fn critical_call_at(
+    client: &BlockchainClient,
+    provider: &Arc<dyn Provider<Ethereum>>,
+    label: &str,
+    tx: TransactionRequest,
+    block: Option<u64>,
+) -> Result<Bytes> {
+    let Some(block) = block else {
+        return critical_call(client, provider, label, tx).await;
+    };
+    match client.quorum() {
+        Some(quorum) => quorum.call_at(label, tx, block).await,
+        None => Ok(provider.call(tx).block(BlockId::number(block)).await?),
+    }
+}
@@ -0,0 +1,224 @@
+use crate::config::ChainConfig;
+use crate::sources::s3_campaign_source::{parse_s3_path, s3_client, S3CampaignSource};
+use crate::transaction_monitor::TransactionReceipt;
+use alloy::primitives::keccak256;
+use anyhow::Result;
+use aws_sdk_s3::primitives::ByteStream;
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+// What one-shot commands print to stdout when they finish
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    // Logs only
+    #[default]
+    Text,
+    // The run report as JSON; logs move to stderr so stdout stays parseable
+    Json,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionRecord {
+    pub label: String,
+    pub hash: String,
+    pub status: String,
+    pub block_number: u64,
+    pub gas_used: String,
+    pub effective_gas_price: String,
+    pub l1_fee: Option<String>,
+    pub total_cost_wei: String,
+    pub estimated_cost_wei: Option<String>,
+}
+
+// Structured record of one job execution, written as JSON for downstream tooling
+#[derive(Debug, Clone, Serialize)]
+pub struct RunReport {
+    pub job: String,
+    pub chain_id: u64,
+    pub dry_run: bool,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub keeper_address: Option<String>,
+    pub config_hash: String,
+    pub campaigns_version: Option<String>,
+    pub reads: BTreeMap<String, Value>,
+    pub transactions: Vec<TransactionRecord>,
+    pub decisions: Vec<String>,
+    pub outcome: String,
+    pub error: Option<String>,
+}
+
+// Shared handle jobs use to fill in the report as they go. Cloning is cheap, so
+// concurrent campaigns of one run can all write to the same report.
+#[derive(Clone)]
+pub struct RunRecorder {
+    report: Arc<Mutex<RunReport>>,
+}
+
+impl RunRecorder {
+    pub fn new(job: &str, config: &ChainConfig, dry_run: bool) -> Self {
+        Self {
+            report: Arc::new(Mutex::new(RunReport {
+                job: job.to_string(),
+                chain_id: config.chain.chain_id,
+                dry_run,
+                started_at: Utc::now(),
+                finished_at: None,
+                keeper_address: None,
+                config_hash: config_hash(config),
+                campaigns_version: None,
+                reads: BTreeMap::new(),
+                transactions: Vec::new(),
+                decisions: Vec::new(),
+                outcome: "running".to_string(),
+                error: None,
+            })),
+        }
+    }
+
+    // Recorder for jobs run outside a reporting runner, e.g. in tests
+    pub fn detached(config: &ChainConfig) -> Self {
+        Self::new("detached", config, false)
+    }
+
+    fn update(&self, f: impl FnOnce(&mut RunReport)) {
+        if let Ok(mut report) = self.report.lock() {
+            f(&mut report);
+        }
+    }
+
+    pub fn keeper_address(&self, address: impl ToString) {
+        self.update(|r| r.keeper_address = Some(address.to_string()));
+    }
+
+    pub fn campaigns_version(&self, version: Option<String>) {
+        self.update(|r| r.campaigns_version = version);
+    }
+
+    // Values are stored as JSON; wei amounts are passed as strings to keep precision
+    pub fn read(&self, key: &str, value: impl Serialize) {
+        let value = serde_json::to_value(value).unwrap_or(Value::Null);
+        self.update(|r| {
+            r.reads.insert(key.to_string(), value);
+        });
+    }
+
+    pub fn transaction(&self, label: &str, receipt: &TransactionReceipt) {
+        self.update(|r| {
+            r.transactions.push(TransactionRecord {
+                label: label.to_string(),
+                hash: format!("{:?}", receipt.hash),
+                status: format!("{:?}", receipt.status),
+                block_number: receipt.block_number,
+                gas_used: receipt.gas_used.to_string(),
+                effective_gas_price: receipt.effective_gas_price.to_string(),
+                l1_fee: receipt.l1_fee.map(|fee| fee.to_string()),
+                total_cost_wei: receipt.total_cost_wei().to_string(),
+                estimated_cost_wei: receipt.estimated_cost_wei.map(|cost| cost.to_string()),
+            })
+        });
+    }
+
+    pub fn decision(&self, decision: impl Into<String>) {
+        let decision = decision.into();
+        self.update(|r| r.decisions.push(decision));
+    }
+
+    pub fn finish<T>(&self, result: &Result<T>) -> RunReport {
+        self.update(|r| {
+            r.finished_at = Some(Utc::now());
+            r.outcome = if result.is_ok() { "success" } else { "failure" }.to_string();
+            r.error = result.as_ref().err().map(|e| format!("{:#}", e));
+        });
+        self.snapshot()
+    }
+
+    pub fn snapshot(&self) -> RunReport {
+        self.report
+            .lock()
+            .map(|r| r.clone())
+            .unwrap_or_else(|poisoned| poisoned.into_inner().clone())
+    }
+}
+
+// Stable fingerprint of the effective (merged, env-substituted) config
+pub fn config_hash(config: &ChainConfig) -> String {
+    let serialized = toml::to_string(config).unwrap_or_default();
+    format!("{:?}", keccak256(serialized.as_bytes()))
+}
+
+impl RunReport {
+    pub fn file_name(&self) -> String {
+        format!(
+            "{}-{}-{}.json",
+            self.started_at.format("%Y%m%dT%H%M%SZ"),
+            self.chain_id,
+            self.job
+        )
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    // Writes to every configured destination; failures are logged, never fatal
+    pub async fn write(&self, config: &ChainConfig) {
+        let settings = &config.report;
+        let json = match self.to_json() {
+            Ok(json) => json,
+            Err(e) => {
+                warn!("⚠️  Failed to serialize run report: {}", e);
+                return;
+            }
+        };
+
+        if let Some(dir) = &settings.dir {
+            let path = PathBuf::from(dir).join(self.file_name());
+            let result = std::fs::create_dir_all(dir).and_then(|_| std::fs::write(&path, &json));
+            match result {
+                Ok(()) => info!("📝 Run report written to {}", path.display()),
+                Err(e) => warn!("⚠️  Failed to write run report {}: {}", path.display(), e),
+            }
+        }
+
+        if let Some(uri) = &settings.s3_uri {
+            match self
+                .upload(uri, settings.s3_region.as_deref(), config, json)
+                .await
+            {
+                Ok(location) => info!("📝 Run report uploaded to {}", location),
+                Err(e) => warn!("⚠️  Failed to upload run report: {}", e),
+            }
+        }
+    }
+
+    async fn upload(
+        &self,
+        uri: &str,
+        region: Option<&str>,
+        config: &ChainConfig,
+        json: String,
+    ) -> Result<String> {
+        let (bucket, prefix) = parse_s3_path(uri)?;
+        let region = S3CampaignSource::resolve_region(region.map(str::to_string), config)?;
+        let key = format!("{}/{}", prefix.trim_end_matches('/'), self.file_name());
+
+        s3_client(&region)
+            .await
+            .put_object()
+            .bucket(&bucket)
+            .key(&key)
+            .content_type("application/json")
+            .body(ByteStream::from(json.into_bytes()))
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("PutObject s3://{}/{} failed: {}", bucket, key, e))?;
+        Ok(format!("s3://{}/{}", bucket, key))
+    }
+}
@@ -1,6 +1,9 @@
+use crate::error::KeeperError;
+use crate::metrics::metrics;
 use anyhow::Result;
 use std::time::Duration;
 use tokio::time::sleep;
+use tracing::{error, info};
 
 #[derive(Debug, Clone)]
 pub struct RetryConfig {
@@ -51,28 +54,41 @@ where
     let mut last_error = None;
 
     while attempt <= retry_config.max_attempts {
-        println!(
+        info!(
             "🔄 {} attempt {}/{}",
             operation_name, attempt, retry_config.max_attempts
         );
 
         match operation().await {
             Ok(result) => {
-                println!("✅ {} succeeded on attempt {}", operation_name, attempt);
+                metrics()
+                    .retry_attempts
+                    .with_label_values(&[operation_name, "success"])
+                    .inc();
+                info!("✅ {} succeeded on attempt {}", operation_name, attempt);
                 return Ok(result);
             }
             Err(e) => {
+                metrics()
+                    .retry_attempts
+                    .with_label_values(&[operation_name, "failure"])
+                    .inc();
                 last_error = Some(e);
-                println!(
+                error!(
                     "❌ {} failed on attempt {}: {}",
                     operation_name,
                     attempt,
                     last_error.as_ref().unwrap()
                 );
 
+                if classify(last_error.as_ref()).is_some_and(KeeperError::is_permanent) {
+                    info!("⛔ {} failed permanently, not retrying", operation_name);
+                    break;
+                }
+
                 if attempt < retry_config.max_attempts {
                     let delay = calculate_delay(attempt, retry_config);
-                    println!("⏳ Waiting {:?} before retry...", delay);
+                    info!("⏳ Waiting {:?} before retry...", delay);
                     sleep(delay).await;
                 }
             }
@@ -81,12 +97,23 @@ where
         attempt += 1;
     }
 
-    Err(anyhow::anyhow!(
+    let message = format!(
         "{} failed after {} attempts. Last error: {}",
         operation_name,
-        retry_config.max_attempts,
-        last_error.unwrap()
-    ))
+        attempt.min(retry_config.max_attempts),
+        last_error.as_ref().unwrap()
+    );
+    // Keep the failure class so the process exit code still reflects it
+    match classify(last_error.as_ref()) {
+        Some(kind) => Err(anyhow::Error::new(kind.clone()).context(message)),
+        None => Err(anyhow::anyhow!(message)),
+    }
+}
+
+// Operations return all kinds of error types; only anyhow errors can carry a class
+fn classify<E: 'static>(error: Option<&E>) -> Option<&KeeperError> {
+    let error = (error? as &dyn std::any::Any).downcast_ref::<anyhow::Error>()?;
+    KeeperError::find(error)
 }
 
 fn calculate_delay(attempt: u32, config: &RetryConfig) -> Duration {
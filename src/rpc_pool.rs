@@ -0,0 +1,463 @@
+use crate::config::ChainSettings;
+use crate::error::KeeperError;
+use crate::metrics::metrics;
+use alloy::primitives::U64;
+use alloy::rpc::client::RpcClient;
+use alloy::rpc::json_rpc::{RequestPacket, ResponsePacket};
+use alloy::transports::http::Http;
+use alloy::transports::{RpcError, TransportError, TransportFut};
+use anyhow::Result;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::Service;
+use tracing::{info, warn};
+use url::Url;
+
+// Cooldowns stop doubling after this many consecutive failures
+const MAX_BACKOFF_STEPS: u32 = 4;
+
+#[derive(Debug, Default)]
+struct EndpointState {
+    consecutive_failures: u32,
+    down_until: Option<Instant>,
+    last_error: Option<String>,
+    block: Option<u64>,
+    lag: u64,
+    lagging: bool,
+}
+
+impl EndpointState {
+    fn is_down(&self, now: Instant) -> bool {
+        self.down_until.is_some_and(|until| until > now)
+    }
+}
+
+struct Endpoint {
+    label: String,
+    priority: u32,
+    weight: u32,
+    transport: Http<reqwest::Client>,
+    state: Mutex<EndpointState>,
+}
+
+impl Endpoint {
+    fn state(&self) -> std::sync::MutexGuard<'_, EndpointState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointHealth {
+    pub endpoint: String,
+    pub priority: u32,
+    pub weight: u32,
+    pub healthy: bool,
+    pub lagging: bool,
+    pub block: Option<u64>,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+}
+
+struct PoolInner {
+    chain_id: String,
+    endpoints: Vec<Endpoint>,
+    max_block_lag: u64,
+    cooldown: Duration,
+    health_check_interval: Duration,
+    // Drives weighted rotation between endpoints of the same priority
+    rotation: AtomicU64,
+}
+
+// Transport over several RPC endpoints. Each request goes to the preferred healthy
+// endpoint and fails over down the list on transport errors and rate limits; failing
+// endpoints cool down, and endpoints lagging behind the best block height are demoted
+// until they catch up.
+#[derive(Clone)]
+pub struct RpcPool {
+    inner: Arc<PoolInner>,
+}
+
+impl RpcPool {
+    pub fn new(settings: &ChainSettings) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(settings.rpc_timeout_seconds))
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to build HTTP client: {}", e))?;
+
+        let mut endpoints: Vec<Endpoint> = Vec::new();
+        for (index, endpoint) in settings.endpoints().iter().enumerate() {
+            // Optional backup endpoints often come from env vars that aren't set
+            if endpoint.url.contains("${") {
+                warn!(
+                    "⚠️  Skipping RPC endpoint #{} with unresolved variable",
+                    index
+                );
+                continue;
+            }
+            let url = Url::parse(&endpoint.url).map_err(|e| {
+                KeeperError::Config(format!("Invalid RPC URL for endpoint #{}: {}", index, e))
+            })?;
+            let mut label = endpoint_label(&url, index);
+            if endpoints.iter().any(|e| e.label == label) {
+                label = format!("{}#{}", label, index);
+            }
+            endpoints.push(Endpoint {
+                label,
+                priority: endpoint.priority,
+                weight: endpoint.weight.max(1),
+                transport: Http::with_client(client.clone(), url),
+                state: Mutex::new(EndpointState::default()),
+            });
+        }
+        if endpoints.is_empty() {
+            return Err(KeeperError::Config(
+                "No RPC endpoint configured: set chain.rpc_url or chain.rpc_endpoints".to_string(),
+            )
+            .into());
+        }
+
+        let pool = Self {
+            inner: Arc::new(PoolInner {
+                chain_id: settings.chain_id.to_string(),
+                endpoints,
+                max_block_lag: settings.max_block_lag,
+                cooldown: Duration::from_secs(settings.endpoint_cooldown_seconds),
+                health_check_interval: Duration::from_secs(
+                    settings.health_check_interval_seconds.max(1),
+                ),
+                rotation: AtomicU64::new(0),
+            }),
+        };
+        for endpoint in &pool.inner.endpoints {
+            pool.set_healthy_gauge(endpoint, true);
+        }
+        Ok(pool)
+    }
+
+    // Single endpoint pool, e.g. for an explicit `--rpc-url`
+    pub fn single(chain_id: u64, rpc_url: &str) -> Result<Self> {
+        Self::new(&ChainSettings::new(chain_id, rpc_url))
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.endpoints.len()
+    }
+
+    #[allow(dead_code)] // Public API
+    pub fn is_empty(&self) -> bool {
+        self.inner.endpoints.is_empty()
+    }
+
+    pub fn labels(&self) -> Vec<String> {
+        self.inner
+            .endpoints
+            .iter()
+            .map(|e| e.label.clone())
+            .collect()
+    }
+
+    pub fn client(&self) -> RpcClient {
+        let is_local = self
+            .inner
+            .endpoints
+            .iter()
+            .all(|e| alloy::transports::utils::guess_local_url(e.transport.url()));
+        RpcClient::new(self.clone(), is_local)
+    }
+
+    pub fn health(&self) -> Vec<EndpointHealth> {
+        let now = Instant::now();
+        self.inner
+            .endpoints
+            .iter()
+            .map(|endpoint| {
+                let state = endpoint.state();
+                EndpointHealth {
+                    endpoint: endpoint.label.clone(),
+                    priority: endpoint.priority,
+                    weight: endpoint.weight,
+                    healthy: !state.is_down(now) && !state.lagging,
+                    lagging: state.lagging,
+                    block: state.block,
+                    consecutive_failures: state.consecutive_failures,
+                    last_error: state.last_error.clone(),
+                }
+            })
+            .collect()
+    }
+
+    // Endpoint indices in the order a request should try them: healthy endpoints by
+    // priority (the first one picked by weight among the best priority), then lagging
+    // ones, then those still cooling down, soonest to recover first. Nothing is ever
+    // excluded, so a request still gets a chance when every endpoint looks bad.
+    fn order(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let mut ranked: Vec<(u8, Option<Instant>, u32, usize)> = self
+            .inner
+            .endpoints
+            .iter()
+            .enumerate()
+            .map(|(index, endpoint)| {
+                let state = endpoint.state();
+                let tier = if state.is_down(now) {
+                    2
+                } else if state.lagging {
+                    1
+                } else {
+                    0
+                };
+                let until = state.down_until.filter(|_| tier == 2);
+                (tier, until, endpoint.priority, index)
+            })
+            .collect();
+        ranked.sort();
+
+        let best: Vec<usize> = ranked
+            .iter()
+            .take_while(|(tier, _, priority, _)| *tier == 0 && *priority == ranked[0].2)
+            .map(|(_, _, _, index)| *index)
+            .collect();
+        let mut order: Vec<usize> = ranked.iter().map(|(_, _, _, index)| *index).collect();
+        if best.len() > 1 {
+            let total: u64 = best
+                .iter()
+                .map(|i| self.inner.endpoints[*i].weight as u64)
+                .sum();
+            let mut slot = self.inner.rotation.fetch_add(1, Ordering::Relaxed) % total;
+            for (position, index) in best.iter().enumerate() {
+                let weight = self.inner.endpoints[*index].weight as u64;
+                if slot < weight {
+                    order[..best.len()].rotate_left(position);
+                    break;
+                }
+                slot -= weight;
+            }
+        }
+        order
+    }
+
+    async fn dispatch(self, request: RequestPacket) -> Result<ResponsePacket, TransportError> {
+        let order = self.order();
+        let mut last = None;
+        for (attempt, index) in order.iter().enumerate() {
+            let endpoint = &self.inner.endpoints[*index];
+            let mut transport = endpoint.transport.clone();
+            let result = transport.call(request.clone()).await;
+            let failure = match &result {
+                Ok(response) if is_rate_limited(response) => Some(("rate_limited", {
+                    response
+                        .first_error_message()
+                        .unwrap_or("rate limited")
+                        .to_string()
+                })),
+                Ok(_) => None,
+                Err(e) if should_fail_over(e) => Some(("error", e.to_string())),
+                // Anything else is the node answering; another endpoint would say the same
+                Err(_) => None,
+            };
+
+            match failure {
+                None => {
+                    self.record_request(endpoint, if result.is_ok() { "ok" } else { "error" });
+                    self.mark_up(endpoint);
+                    return result;
+                }
+                Some((kind, reason)) => {
+                    self.record_request(endpoint, kind);
+                    self.mark_down(endpoint, &reason);
+                    if attempt + 1 < order.len() {
+                        warn!(
+                            endpoint = %endpoint.label,
+                            "🔀 RPC endpoint {} failed ({}), failing over to {}",
+                            endpoint.label,
+                            truncate(&reason),
+                            self.inner.endpoints[order[attempt + 1]].label
+                        );
+                    }
+                    last = Some(result);
+                }
+            }
+        }
+        last.expect("pool has at least one endpoint")
+    }
+
+    fn record_request(&self, endpoint: &Endpoint, result: &str) {
+        metrics()
+            .rpc_requests
+            .with_label_values(&[&self.inner.chain_id, &endpoint.label, result])
+            .inc();
+    }
+
+    fn set_healthy_gauge(&self, endpoint: &Endpoint, healthy: bool) {
+        metrics()
+            .rpc_endpoint_healthy
+            .with_label_values(&[&self.inner.chain_id, &endpoint.label])
+            .set(if healthy { 1.0 } else { 0.0 });
+    }
+
+    fn mark_down(&self, endpoint: &Endpoint, reason: &str) {
+        let mut state = endpoint.state();
+        state.consecutive_failures += 1;
+        let steps = (state.consecutive_failures - 1).min(MAX_BACKOFF_STEPS);
+        let cooldown = self.inner.cooldown * 2u32.pow(steps);
+        state.down_until = Some(Instant::now() + cooldown);
+        state.last_error = Some(truncate(reason));
+        warn!(
+            endpoint = %endpoint.label,
+            failures = state.consecutive_failures,
+            "🔻 RPC endpoint {} marked down for {}s",
+            endpoint.label,
+            cooldown.as_secs()
+        );
+        drop(state);
+        self.set_healthy_gauge(endpoint, false);
+    }
+
+    fn mark_up(&self, endpoint: &Endpoint) {
+        let mut state = endpoint.state();
+        if state.consecutive_failures == 0 {
+            return;
+        }
+        info!(
+            endpoint = %endpoint.label,
+            "✅ RPC endpoint {} recovered after {} failure(s)",
+            endpoint.label,
+            state.consecutive_failures
+        );
+        state.consecutive_failures = 0;
+        state.down_until = None;
+        let lagging = state.lagging;
+        drop(state);
+        self.set_healthy_gauge(endpoint, !lagging);
+    }
+
+    // Polls every endpoint's block number and demotes those more than `max_block_lag`
+    // behind the best one. Unreachable endpoints are marked down.
+    pub async fn check_health(&self) {
+        let checks = self.inner.endpoints.iter().map(|endpoint| async move {
+            let client = RpcClient::new(endpoint.transport.clone(), false);
+            let result = client.request_noparams::<U64>("eth_blockNumber").await;
+            (endpoint, result.map(|block| block.to::<u64>()))
+        });
+        let results = futures::future::join_all(checks).await;
+
+        let head = results
+            .iter()
+            .filter_map(|(_, result)| result.as_ref().ok().copied())
+            .max();
+        for (endpoint, result) in results {
+            let block = match result {
+                Ok(block) => block,
+                Err(e) => {
+                    self.record_request(endpoint, "error");
+                    self.mark_down(endpoint, &e.to_string());
+                    continue;
+                }
+            };
+            self.record_request(endpoint, "ok");
+            self.mark_up(endpoint);
+
+            let lag = head.unwrap_or(block).saturating_sub(block);
+            let lagging = lag > self.inner.max_block_lag;
+            let mut state = endpoint.state();
+            let was_lagging = state.lagging;
+            state.block = Some(block);
+            state.lag = lag;
+            state.lagging = lagging;
+            let down = state.is_down(Instant::now());
+            drop(state);
+
+            if lagging && !was_lagging {
+                warn!(
+                    endpoint = %endpoint.label,
+                    "🐢 RPC endpoint {} is {} blocks behind (block {}), demoting",
+                    endpoint.label,
+                    lag,
+                    block
+                );
+            } else if !lagging && was_lagging {
+                info!(
+                    endpoint = %endpoint.label,
+                    "✅ RPC endpoint {} caught up (block {})",
+                    endpoint.label,
+                    block
+                );
+            }
+            metrics()
+                .rpc_endpoint_block_lag
+                .with_label_values(&[&self.inner.chain_id, &endpoint.label])
+                .set(lag as f64);
+            self.set_healthy_gauge(endpoint, !lagging && !down);
+        }
+    }
+
+    // Periodic health checks for as long as the pool is in use. A single endpoint has
+    // nothing to compare against or fail over to, so it isn't checked.
+    pub fn spawn_health_checks(&self) {
+        if self.len() < 2 {
+            return;
+        }
+        let weak: Weak<PoolInner> = Arc::downgrade(&self.inner);
+        let interval = self.inner.health_check_interval;
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let Some(inner) = weak.upgrade() else {
+                    break;
+                };
+                RpcPool { inner }.check_health().await;
+            }
+        });
+    }
+}
+
+impl Service<RequestPacket> for RpcPool {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        Box::pin(self.clone().dispatch(request))
+    }
+}
+
+// Host only: provider URLs usually carry the API key in the path or query
+pub(crate) fn endpoint_label(url: &Url, index: usize) -> String {
+    match (url.host_str(), url.port()) {
+        (Some(host), Some(port)) => format!("{}:{}", host, port),
+        (Some(host), None) => host.to_string(),
+        _ => format!("endpoint-{}", index),
+    }
+}
+
+// Connection failures, timeouts, HTTP errors (429, 5xx, ...) and unparseable bodies
+// are the endpoint's fault; error responses from the node are not
+fn should_fail_over(error: &TransportError) -> bool {
+    matches!(error, RpcError::Transport(_) | RpcError::DeserError { .. })
+}
+
+// Some providers answer rate limits with HTTP 200 and a JSON-RPC error
+fn is_rate_limited(response: &ResponsePacket) -> bool {
+    response.iter_errors().any(|error| {
+        let message = error.message.to_lowercase();
+        error.code == 429
+            || error.code == -32005
+            || message.contains("rate limit")
+            || message.contains("too many requests")
+    })
+}
+
+fn truncate(reason: &str) -> String {
+    const MAX: usize = 200;
+    match reason.char_indices().nth(MAX) {
+        Some((cut, _)) => format!("{}...", &reason[..cut]),
+        None => reason.to_string(),
+    }
+}
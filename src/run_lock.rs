@@ -0,0 +1,442 @@
+use crate::config::{ChainConfig, LockBackendKind};
+use crate::error::KeeperError;
+use crate::sources::s3_campaign_source::{parse_s3_path, s3_client, S3CampaignSource};
+use anyhow::Result;
+use aws_sdk_s3::config::http::HttpResponse;
+use aws_sdk_s3::error::SdkError;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client as S3Client;
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+// Contents of a lock: who holds it and until when (unix seconds)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LockRecord {
+    pub holder: String,
+    pub job: String,
+    pub acquired_at: u64,
+    pub expires_at: u64,
+}
+
+impl LockRecord {
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at <= now
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AcquireOutcome {
+    Acquired,
+    HeldBy(LockRecord),
+}
+
+#[async_trait::async_trait]
+pub trait LockBackend: Send + Sync {
+    // Takes the lock if it is free, expired, or already ours
+    async fn try_acquire(&self, name: &str, record: &LockRecord) -> Result<AcquireOutcome>;
+    // Extends our lease; returns false if the lock is no longer ours
+    async fn renew(&self, name: &str, record: &LockRecord) -> Result<bool>;
+    async fn release(&self, name: &str, holder: &str) -> Result<()>;
+}
+
+// Local backend: one JSON record per lock, read-modify-write under an flock
+pub struct FileLockBackend {
+    dir: PathBuf,
+}
+
+impl FileLockBackend {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn with_guard<T>(
+        &self,
+        name: &str,
+        f: impl FnOnce(&PathBuf, Option<LockRecord>) -> Result<T>,
+    ) -> Result<T> {
+        fs::create_dir_all(&self.dir)?;
+        let guard = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.dir.join(format!("{}.guard", name)))?;
+        guard.lock_exclusive()?;
+
+        let record_path = self.dir.join(format!("{}.json", name));
+        let current = match fs::read_to_string(&record_path) {
+            Ok(content) => serde_json::from_str(&content).ok(),
+            Err(_) => None,
+        };
+        let result = f(&record_path, current);
+
+        FileExt::unlock(&guard)?;
+        result
+    }
+}
+
+#[async_trait::async_trait]
+impl LockBackend for FileLockBackend {
+    async fn try_acquire(&self, name: &str, record: &LockRecord) -> Result<AcquireOutcome> {
+        let now = record.acquired_at;
+        self.with_guard(name, |path, current| match current {
+            Some(existing) if existing.holder != record.holder && !existing.is_expired(now) => {
+                Ok(AcquireOutcome::HeldBy(existing))
+            }
+            _ => {
+                fs::write(path, serde_json::to_vec_pretty(record)?)?;
+                Ok(AcquireOutcome::Acquired)
+            }
+        })
+    }
+
+    async fn renew(&self, name: &str, record: &LockRecord) -> Result<bool> {
+        self.with_guard(name, |path, current| match current {
+            Some(existing) if existing.holder == record.holder => {
+                fs::write(path, serde_json::to_vec_pretty(record)?)?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        })
+    }
+
+    async fn release(&self, name: &str, holder: &str) -> Result<()> {
+        self.with_guard(name, |path, current| {
+            if matches!(current, Some(existing) if existing.holder == holder) {
+                fs::remove_file(path)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+// Shared backend: one object per lock, created with If-None-Match and taken over or
+// renewed with If-Match on the ETag, so only one writer can win
+pub struct S3LockBackend {
+    client: S3Client,
+    bucket: String,
+    prefix: String,
+}
+
+impl S3LockBackend {
+    pub fn new(client: S3Client, bucket: String, prefix: String) -> Self {
+        Self {
+            client,
+            bucket,
+            prefix,
+        }
+    }
+
+    fn key(&self, name: &str) -> String {
+        format!("{}/{}.json", self.prefix.trim_end_matches('/'), name)
+    }
+
+    async fn read(&self, name: &str) -> Result<Option<(LockRecord, String)>> {
+        let response = match self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(self.key(name))
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) if http_status(&e) == Some(404) => return Ok(None),
+            Err(e) => return Err(anyhow::anyhow!("Failed to read lock {}: {}", name, e)),
+        };
+
+        let etag = response.e_tag().unwrap_or_default().to_string();
+        let bytes = response
+            .body
+            .collect()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read lock body: {}", e))?;
+        let record = serde_json::from_slice(&bytes.to_vec())?;
+        Ok(Some((record, etag)))
+    }
+
+    // Returns false when the precondition failed, i.e. another writer got there first
+    async fn write(&self, name: &str, record: &LockRecord, etag: Option<&str>) -> Result<bool> {
+        let request = self
+            .client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(self.key(name))
+            .content_type("application/json")
+            .body(ByteStream::from(serde_json::to_vec_pretty(record)?));
+        let request = match etag {
+            Some(etag) => request.if_match(etag),
+            None => request.if_none_match("*"),
+        };
+
+        match request.send().await {
+            Ok(_) => Ok(true),
+            Err(e) if matches!(http_status(&e), Some(409) | Some(412)) => Ok(false),
+            Err(e) => Err(anyhow::anyhow!("Failed to write lock {}: {}", name, e)),
+        }
+    }
+}
+
+fn http_status<E>(err: &SdkError<E, HttpResponse>) -> Option<u16> {
+    err.raw_response()
+        .map(|response| response.status().as_u16())
+}
+
+#[async_trait::async_trait]
+impl LockBackend for S3LockBackend {
+    async fn try_acquire(&self, name: &str, record: &LockRecord) -> Result<AcquireOutcome> {
+        if self.write(name, record, None).await? {
+            return Ok(AcquireOutcome::Acquired);
+        }
+
+        let Some((existing, etag)) = self.read(name).await? else {
+            // Released between our write and read; try once more
+            if self.write(name, record, None).await? {
+                return Ok(AcquireOutcome::Acquired);
+            }
+            // Lost that race too: report whoever won it
+            return match self.read(name).await? {
+                Some((holder, _)) => Ok(AcquireOutcome::HeldBy(holder)),
+                None => Err(KeeperError::LockHeld(format!(
+                    "Run lock {} changed hands while acquiring it",
+                    name
+                ))
+                .into()),
+            };
+        };
+
+        if existing.holder != record.holder && !existing.is_expired(record.acquired_at) {
+            return Ok(AcquireOutcome::HeldBy(existing));
+        }
+
+        if self.write(name, record, Some(&etag)).await? {
+            Ok(AcquireOutcome::Acquired)
+        } else {
+            // Someone else took over the expired lease first
+            Ok(self
+                .read(name)
+                .await?
+                .map(|(holder, _)| AcquireOutcome::HeldBy(holder))
+                .unwrap_or(AcquireOutcome::HeldBy(existing)))
+        }
+    }
+
+    async fn renew(&self, name: &str, record: &LockRecord) -> Result<bool> {
+        match self.read(name).await? {
+            Some((existing, etag)) if existing.holder == record.holder => {
+                self.write(name, record, Some(&etag)).await
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn release(&self, name: &str, holder: &str) -> Result<()> {
+        if let Some((existing, _)) = self.read(name).await? {
+            if existing.holder == holder {
+                self.client
+                    .delete_object()
+                    .bucket(&self.bucket)
+                    .key(self.key(name))
+                    .send()
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to release lock {}: {}", name, e))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Held lock with a background task renewing the lease. Call `release` when done; if the
+// process dies instead, the lease simply expires.
+pub struct RunLockGuard {
+    backend: Arc<dyn LockBackend>,
+    name: String,
+    holder: String,
+    renewal: JoinHandle<()>,
+    lost: watch::Receiver<bool>,
+}
+
+impl RunLockGuard {
+    // Runs `work` while the lease is ours; if renewal finds another instance holding the
+    // lock, `work` is dropped before it can send anything else
+    pub async fn guard<T>(&self, work: impl Future<Output = Result<T>>) -> Result<T> {
+        let mut lost = self.lost.clone();
+        tokio::select! {
+            result = work => result,
+            _ = lost.wait_for(|lost| *lost) => Err(KeeperError::LockHeld(format!(
+                "Run lock {} was lost mid-run, another instance holds it",
+                self.name
+            ))
+            .into()),
+        }
+    }
+
+    pub async fn release(self) {
+        self.renewal.abort();
+        match self.backend.release(&self.name, &self.holder).await {
+            Ok(()) => info!("🔓 Released run lock {}", self.name),
+            Err(e) => warn!("⚠️  Failed to release run lock {}: {}", self.name, e),
+        }
+    }
+}
+
+impl Drop for RunLockGuard {
+    fn drop(&mut self) {
+        self.renewal.abort();
+    }
+}
+
+pub struct RunLock {
+    backend: Arc<dyn LockBackend>,
+    ttl: Duration,
+}
+
+impl RunLock {
+    pub fn new(backend: Arc<dyn LockBackend>, ttl: Duration) -> Self {
+        Self { backend, ttl }
+    }
+
+    pub async fn from_config(config: &ChainConfig) -> Result<Self> {
+        let settings = &config.lock;
+        let backend: Arc<dyn LockBackend> = match settings.backend {
+            LockBackendKind::File => Arc::new(FileLockBackend::new(settings.file_dir())),
+            LockBackendKind::S3 => {
+                let uri = settings.s3_uri.as_deref().ok_or_else(|| {
+                    anyhow::anyhow!("lock.s3_uri is required for the s3 lock backend")
+                })?;
+                let region = S3CampaignSource::resolve_region(settings.s3_region.clone(), config)?;
+                let (bucket, prefix) = parse_s3_path(uri)?;
+                Arc::new(S3LockBackend::new(s3_client(&region).await, bucket, prefix))
+            }
+        };
+        Ok(Self::new(
+            backend,
+            Duration::from_secs(settings.ttl_seconds.max(1)),
+        ))
+    }
+
+    // Convenience for callers: build the configured backend and take this chain's lock
+    pub async fn acquire_for_job(config: &ChainConfig, job: &str) -> Result<RunLockGuard> {
+        let lock = Self::from_config(config).await?;
+        lock.acquire(&Self::lock_name(config), job).await
+    }
+
+    // One lock per signing key and chain: that is what nonces and payouts race on
+    pub fn lock_name(config: &ChainConfig) -> String {
+        let key_id = config
+            .kms
+            .as_ref()
+            .map(|kms| kms.key_id.as_str())
+            .unwrap_or("no-kms");
+        let sanitized: String = key_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("keeper-{}-{}", config.chain.chain_id, sanitized)
+    }
+
+    pub async fn acquire(&self, name: &str, job: &str) -> Result<RunLockGuard> {
+        let holder = holder_id();
+        let record = self.record(&holder, job);
+
+        match self.backend.try_acquire(name, &record).await? {
+            AcquireOutcome::Acquired => {
+                info!(
+                    "🔒 Acquired run lock {} as {} (lease {}s)",
+                    name,
+                    holder,
+                    self.ttl.as_secs()
+                );
+            }
+            AcquireOutcome::HeldBy(existing) => {
+                info!(
+                    "🔒 Run lock {} is held by {} (job {}, acquired at {}, expires at {})",
+                    name, existing.holder, existing.job, existing.acquired_at, existing.expires_at
+                );
+                return Err(KeeperError::LockHeld(format!(
+                    "Run lock {} is held by {} (job {}) until {}",
+                    name, existing.holder, existing.job, existing.expires_at
+                ))
+                .into());
+            }
+        }
+
+        let (lost_tx, lost) = watch::channel(false);
+        let renewal = {
+            let backend = self.backend.clone();
+            let name = name.to_string();
+            let holder = holder.clone();
+            let job = job.to_string();
+            let ttl = self.ttl;
+            tokio::spawn(async move {
+                loop {
+                    tokio::time::sleep(ttl / 3).await;
+                    let record = Self::build_record(&holder, &job, ttl);
+                    match backend.renew(&name, &record).await {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            warn!("⚠️  Run lock {} was lost, another instance holds it", name);
+                            let _ = lost_tx.send(true);
+                            return;
+                        }
+                        Err(e) => warn!("⚠️  Failed to renew run lock {}: {}", name, e),
+                    }
+                }
+            })
+        };
+
+        Ok(RunLockGuard {
+            backend: self.backend.clone(),
+            name: name.to_string(),
+            holder,
+            renewal,
+            lost,
+        })
+    }
+
+    fn record(&self, holder: &str, job: &str) -> LockRecord {
+        Self::build_record(holder, job, self.ttl)
+    }
+
+    fn build_record(holder: &str, job: &str, ttl: Duration) -> LockRecord {
+        let now = unix_now();
+        LockRecord {
+            holder: holder.to_string(),
+            job: job.to_string(),
+            acquired_at: now,
+            expires_at: now + ttl.as_secs(),
+        }
+    }
+}
+
+// Unique per acquisition, so two jobs in one process never share (or release) a lease
+fn holder_id() -> String {
+    // HOSTNAME is the pod name in Kubernetes
+    let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "unknown-host".to_string());
+    format!(
+        "{}:{}:{:016x}",
+        host,
+        std::process::id(),
+        rand::random::<u64>()
+    )
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
@@ -0,0 +1,312 @@
+use crate::jobs::JobContext;
+use crate::kms_signer::KmsSigner;
+use crate::metrics::metrics;
+use alloy::primitives::utils::format_ether;
+use alloy::primitives::Address;
+use anyhow::Result;
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Json};
+use axum::routing::get;
+use axum::Router;
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+// Upper bound for each RPC / KMS round-trip made by a probe
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+// A reachable KMS key is taken as still reachable for this long, so frequent probes
+// don't call GetPublicKey (billed and rate limited) every time. Failures aren't cached.
+const KMS_CHECK_TTL: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone)]
+pub struct JobRun {
+    pub job: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+}
+
+impl JobRun {
+    pub fn outcome(&self) -> &'static str {
+        match (&self.finished_at, &self.error) {
+            (None, _) => "running",
+            (Some(_), None) => "success",
+            (Some(_), Some(_)) => "failure",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ChainCheck {
+    pub chain_id: u64,
+    pub rpc: Result<(), String>,
+    pub kms: Result<(), String>,
+}
+
+impl ChainCheck {
+    pub fn is_ready(&self) -> bool {
+        self.rpc.is_ok() && self.kms.is_ok()
+    }
+}
+
+#[derive(Default)]
+struct StatusState {
+    runs: BTreeMap<String, JobRun>,
+    contexts: Vec<Arc<JobContext>>,
+    // Last successful KMS check per chain and key address
+    kms_checked_at: HashMap<(u64, Address), Instant>,
+}
+
+// Shared view of what the keeper is doing, fed by the job runners and read by the
+// HTTP endpoints
+#[derive(Clone, Default)]
+pub struct KeeperStatus {
+    state: Arc<RwLock<StatusState>>,
+}
+
+impl KeeperStatus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Connected chains are what readiness and balances are checked against
+    pub fn register_context(&self, ctx: Arc<JobContext>) {
+        if let Ok(mut state) = self.state.write() {
+            if !state.contexts.iter().any(|known| Arc::ptr_eq(known, &ctx)) {
+                state.contexts.push(ctx);
+            }
+        }
+    }
+
+    pub fn run_started(&self, job: &str) {
+        if let Ok(mut state) = self.state.write() {
+            state.runs.insert(
+                job.to_string(),
+                JobRun {
+                    job: job.to_string(),
+                    started_at: Utc::now(),
+                    finished_at: None,
+                    error: None,
+                },
+            );
+        }
+    }
+
+    pub fn run_finished<T>(&self, job: &str, result: &Result<T>) {
+        if let Ok(mut state) = self.state.write() {
+            if let Some(run) = state.runs.get_mut(job) {
+                run.finished_at = Some(Utc::now());
+                run.error = result.as_ref().err().map(|e| e.to_string());
+            }
+        }
+    }
+
+    pub fn last_runs(&self) -> Vec<JobRun> {
+        self.state
+            .read()
+            .map(|state| state.runs.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn contexts(&self) -> Vec<Arc<JobContext>> {
+        self.state
+            .read()
+            .map(|state| state.contexts.clone())
+            .unwrap_or_default()
+    }
+
+    // Ready once at least one chain is connected and every connected chain answers with
+    // the expected chain id and a usable KMS key
+    pub async fn readiness(&self) -> (bool, Vec<ChainCheck>) {
+        let contexts = self.contexts();
+        let mut checks = Vec::new();
+        for ctx in &contexts {
+            checks.push(self.check_chain(ctx).await);
+        }
+        let ready = !checks.is_empty() && checks.iter().all(ChainCheck::is_ready);
+        (ready, checks)
+    }
+
+    pub async fn status(&self) -> Value {
+        let runs: Vec<Value> = self
+            .last_runs()
+            .into_iter()
+            .map(|run| {
+                json!({
+                    "job": run.job,
+                    "outcome": run.outcome(),
+                    "started_at": run.started_at.to_rfc3339(),
+                    "finished_at": run.finished_at.map(|t| t.to_rfc3339()),
+                    "error": run.error,
+                })
+            })
+            .collect();
+
+        let mut chains = Vec::new();
+        for ctx in self.contexts() {
+            let client = ctx.client();
+            let balance = tokio::time::timeout(
+                CHECK_TIMEOUT,
+                client.provider().get_balance(client.keeper_address()),
+            )
+            .await;
+            let (balance_wei, balance_eth, balance_error) = match balance {
+                Ok(Ok(wei)) => (Some(wei.to_string()), Some(format_ether(wei)), None),
+                Ok(Err(e)) => (None, None, Some(e.to_string())),
+                Err(_) => (None, None, Some("timed out".to_string())),
+            };
+            let transactions: Vec<Value> = client
+                .recent_transactions()
+                .into_iter()
+                .rev()
+                .map(|tx| {
+                    json!({
+                        "hash": format!("{:?}", tx.hash),
+                        "nonce": tx.nonce,
+                        "sent_at": tx.sent_at.to_rfc3339(),
+                    })
+                })
+                .collect();
+
+            chains.push(json!({
+                "chain_id": ctx.chain_id(),
+                "keeper_address": client.keeper_address().to_string(),
+                "gas_balance_wei": balance_wei,
+                "gas_balance_eth": balance_eth,
+                "gas_balance_error": balance_error,
+                "last_transactions": transactions,
+                "rpc_endpoints": client.rpc_health(),
+            }));
+        }
+
+        json!({ "jobs": runs, "chains": chains })
+    }
+
+    async fn check_chain(&self, ctx: &JobContext) -> ChainCheck {
+        let rpc = match tokio::time::timeout(CHECK_TIMEOUT, ctx.client().provider().get_chain_id())
+            .await
+        {
+            Ok(Ok(chain_id)) if chain_id == ctx.chain_id() => Ok(()),
+            Ok(Ok(chain_id)) => Err(format!(
+                "chain id mismatch: expected {}, got {}",
+                ctx.chain_id(),
+                chain_id
+            )),
+            Ok(Err(e)) => Err(format!("RPC unreachable: {}", e)),
+            Err(_) => Err("RPC timed out".to_string()),
+        };
+
+        // Contexts built around a test provider have no KMS signer to check
+        let kms = match ctx.signer() {
+            Some(signer) => self.check_kms(ctx.chain_id(), signer).await,
+            None => Ok(()),
+        };
+
+        ChainCheck {
+            chain_id: ctx.chain_id(),
+            rpc,
+            kms,
+        }
+    }
+
+    async fn check_kms(&self, chain_id: u64, signer: &KmsSigner) -> Result<(), String> {
+        let key = (chain_id, signer.address());
+        let cached = self
+            .state
+            .read()
+            .ok()
+            .and_then(|state| state.kms_checked_at.get(&key).copied())
+            .is_some_and(|checked_at| checked_at.elapsed() < KMS_CHECK_TTL);
+        if cached {
+            return Ok(());
+        }
+
+        let result = match tokio::time::timeout(CHECK_TIMEOUT, signer.check_available()).await {
+            Ok(result) => result.map_err(|e| e.to_string()),
+            Err(_) => Err("KMS timed out".to_string()),
+        };
+        if let Ok(mut state) = self.state.write() {
+            match &result {
+                Ok(()) => state.kms_checked_at.insert(key, Instant::now()),
+                Err(_) => state.kms_checked_at.remove(&key),
+            };
+        }
+        result
+    }
+}
+
+pub fn router(status: KeeperStatus) -> Router {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .route("/status", get(status_handler))
+        .route("/metrics", get(metrics_handler))
+        .with_state(status)
+}
+
+// Binds before returning so address errors surface immediately; the server then runs
+// in the background until the returned handle is aborted or the process exits
+pub async fn spawn(addr: SocketAddr, status: KeeperStatus) -> Result<(SocketAddr, JoinHandle<()>)> {
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to bind HTTP server on {}: {}", addr, e))?;
+    let local_addr = listener.local_addr()?;
+    info!("🩺 HTTP server listening on {}", local_addr);
+
+    let handle = tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, router(status)).await {
+            error!("❌ HTTP server stopped: {}", e);
+        }
+    });
+    Ok((local_addr, handle))
+}
+
+async fn healthz() -> &'static str {
+    "ok"
+}
+
+async fn readyz(State(status): State<KeeperStatus>) -> impl IntoResponse {
+    let (ready, checks) = status.readiness().await;
+    let chains: Vec<Value> = checks
+        .iter()
+        .map(|check| {
+            json!({
+                "chain_id": check.chain_id,
+                "rpc": check.rpc.as_ref().err().map_or("ok", |e| e.as_str()),
+                "kms": check.kms.as_ref().err().map_or("ok", |e| e.as_str()),
+            })
+        })
+        .collect();
+    let code = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (code, Json(json!({ "ready": ready, "chains": chains })))
+}
+
+async fn status_handler(State(status): State<KeeperStatus>) -> Json<Value> {
+    Json(status.status().await)
+}
+
+async fn metrics_handler(State(status): State<KeeperStatus>) -> impl IntoResponse {
+    // Refresh balances at scrape time so the gauges are never stale. Chains are read
+    // concurrently, and one that doesn't answer keeps its last value.
+    let contexts = status.contexts();
+    futures::future::join_all(
+        contexts
+            .iter()
+            .map(|ctx| tokio::time::timeout(CHECK_TIMEOUT, ctx.native_balance())),
+    )
+    .await;
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics().render(),
+    )
+}
@@ -1,3 +1,4 @@
+use crate::config::ChainConfig;
 use crate::jobs::boost_rewards::{CampaignConfig, CampaignConfigSource, CampaignStatus};
 use alloy::primitives::Address;
 use anyhow::Result;
@@ -5,7 +6,9 @@ use aws_sdk_s3::Client as S3Client;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
+use std::sync::Mutex;
 use toml;
+use tracing::info;
 
 #[derive(Debug, Deserialize, Serialize)]
 struct S3CampaignsConfig {
@@ -26,6 +29,8 @@ pub struct S3CampaignSource {
     s3_client: S3Client,
     bucket: String,
     key: String,
+    // VersionId (or ETag on unversioned buckets) of the last object read
+    version: Mutex<Option<String>>,
 }
 
 impl S3CampaignSource {
@@ -34,8 +39,51 @@ impl S3CampaignSource {
             s3_client,
             bucket,
             key,
+            version: Mutex::new(None),
         }
     }
+
+    // Accepts both s3://bucket/key and bucket/key
+    pub async fn from_uri(campaigns_s3: &str, region: &str) -> Result<Self> {
+        let (bucket, key) = parse_s3_path(campaigns_s3)?;
+
+        // Initialize S3 client (same pattern as KMS)
+        info!("🔧 Initializing S3 client...");
+        info!("   Region: {}", region);
+        info!("   Bucket: {}", bucket);
+        info!("   Key: {}", key);
+
+        Ok(Self::new(s3_client(region).await, bucket, key))
+    }
+
+    // Get S3 region: CLI arg -> env var -> KMS region
+    pub fn resolve_region(explicit: Option<String>, config: &ChainConfig) -> Result<String> {
+        explicit
+            .or_else(|| std::env::var("S3_REGION").ok())
+            .or_else(|| std::env::var("AWS_REGION").ok())
+            .or_else(|| config.kms.as_ref().and_then(|kms| kms.region.clone()))
+            .ok_or_else(|| {
+                anyhow::anyhow!("S3 region not specified. Use --s3-region or set S3_REGION")
+            })
+    }
+}
+
+pub async fn s3_client(region: &str) -> S3Client {
+    let aws_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
+        .region(aws_config::Region::new(region.to_string()))
+        .load()
+        .await;
+    S3Client::new(&aws_config)
+}
+
+// Splits s3://bucket/key or bucket/key into (bucket, key)
+pub fn parse_s3_path(path: &str) -> Result<(String, String)> {
+    let stripped = path.strip_prefix("s3://").unwrap_or(path);
+    let parts: Vec<&str> = stripped.splitn(2, '/').collect();
+    if parts.len() != 2 || parts[0].is_empty() || parts[1].is_empty() {
+        return Err(anyhow::anyhow!("Invalid S3 path format: {}", path));
+    }
+    Ok((parts[0].to_string(), parts[1].to_string()))
 }
 
 #[async_trait::async_trait]
@@ -66,6 +114,14 @@ impl CampaignConfigSource for S3CampaignSource {
                 )
             })?;
 
+        let version = response
+            .version_id()
+            .or(response.e_tag())
+            .map(str::to_string);
+        if let Ok(mut last) = self.version.lock() {
+            *last = version;
+        }
+
         // Read body
         let bytes = response
             .body
@@ -148,4 +204,7 @@ impl CampaignConfigSource for S3CampaignSource {
 
         Ok(campaigns)
     }
+    fn version(&self) -> Option<String> {
+        self.version.lock().ok().and_then(|v| v.clone())
+    }
 }
@@ -0,0 +1,98 @@
+use crate::config::ChainSettings;
+use crate::error::KeeperError;
+use crate::rpc_pool::endpoint_label;
+use alloy::network::Ethereum;
+use alloy::providers::{IpcConnect, Provider, ProviderBuilder, WsConnect};
+use alloy::pubsub::Subscription;
+use alloy::rpc::types::Header;
+use anyhow::Result;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+use url::Url;
+
+#[derive(Debug, Clone)]
+enum SubscriptionEndpoint {
+    Ws(String),
+    Ipc(PathBuf),
+}
+
+// WebSocket or IPC connection for newHeads subscriptions, connected on first use and
+// shared by everything waiting on blocks. Requests still go through the HTTP pool.
+#[derive(Clone)]
+pub struct SubscriptionProvider {
+    endpoint: SubscriptionEndpoint,
+    label: String,
+    provider: Arc<Mutex<Option<Arc<dyn Provider<Ethereum>>>>>,
+}
+
+impl SubscriptionProvider {
+    // `ipc_path` wins over `ws_url`; None when neither is set
+    pub fn from_settings(settings: &ChainSettings) -> Result<Option<Self>> {
+        let endpoint = match (settings.ipc_path(), settings.ws_url()) {
+            (Some(path), _) => SubscriptionEndpoint::Ipc(PathBuf::from(path)),
+            (None, Some(url)) => SubscriptionEndpoint::Ws(url.to_string()),
+            (None, None) => return Ok(None),
+        };
+        let label = match &endpoint {
+            SubscriptionEndpoint::Ws(url) => {
+                let url = Url::parse(url)
+                    .map_err(|e| KeeperError::Config(format!("Invalid WebSocket URL: {}", e)))?;
+                endpoint_label(&url, 0)
+            }
+            SubscriptionEndpoint::Ipc(path) => path.display().to_string(),
+        };
+        Ok(Some(Self {
+            endpoint,
+            label,
+            provider: Arc::new(Mutex::new(None)),
+        }))
+    }
+
+    // Host or socket path, safe to log
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    // The connected provider, reconnecting if an earlier connection was dropped
+    pub async fn provider(&self) -> Result<Arc<dyn Provider<Ethereum>>> {
+        let mut provider = self.provider.lock().await;
+        if let Some(provider) = provider.as_ref() {
+            return Ok(provider.clone());
+        }
+        let connected: Arc<dyn Provider<Ethereum>> = match &self.endpoint {
+            SubscriptionEndpoint::Ws(url) => Arc::new(
+                ProviderBuilder::new()
+                    .connect_ws(WsConnect::new(url.clone()))
+                    .await
+                    .map_err(|e| KeeperError::Rpc(format!("{}: {}", self.label, e)))?,
+            ),
+            SubscriptionEndpoint::Ipc(path) => Arc::new(
+                ProviderBuilder::new()
+                    .connect_ipc(IpcConnect::new(path.clone()))
+                    .await
+                    .map_err(|e| KeeperError::Rpc(format!("{}: {}", self.label, e)))?,
+            ),
+        };
+        info!("📡 Subscription provider connected: {}", self.label);
+        *provider = Some(connected.clone());
+        Ok(connected)
+    }
+
+    // A failed subscribe drops the connection so the next call starts a fresh one
+    pub async fn subscribe_heads(&self) -> Result<Subscription<Header>> {
+        let provider = self.provider().await?;
+        match provider.subscribe_blocks().await {
+            Ok(heads) => Ok(heads),
+            Err(e) => {
+                warn!(
+                    "⚠️  newHeads subscription on {} failed, reconnecting next time: {}",
+                    self.label, e
+                );
+                self.provider.lock().await.take();
+                Err(KeeperError::Rpc(format!("{}: {}", self.label, e)).into())
+            }
+        }
+    }
+}
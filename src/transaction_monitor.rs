@@ -1,9 +1,17 @@
+use crate::metrics::metrics;
+use crate::subscription_provider::SubscriptionProvider;
 use alloy::network::Ethereum;
 use alloy::primitives::{B256, U256};
 use alloy::providers::Provider;
+use alloy::pubsub::Subscription;
+use alloy::rpc::types::Header;
+use alloy::serde::WithOtherFields;
 use anyhow::Result;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::MissedTickBehavior;
+use tracing::{error, info, warn};
 
 #[derive(Debug, Clone)]
 pub struct TransactionReceipt {
@@ -12,6 +20,18 @@ pub struct TransactionReceipt {
     pub block_number: u64,
     pub gas_used: U256,
     pub status: TransactionStatus,
+    pub effective_gas_price: u128,
+    // From the receipt's `l1Fee` on OP-stack chains
+    pub l1_fee: Option<U256>,
+    // Set when the sending client recorded one
+    pub estimated_cost_wei: Option<U256>,
+}
+
+impl TransactionReceipt {
+    // L2 execution plus the L1 data fee
+    pub fn total_cost_wei(&self) -> U256 {
+        self.gas_used * U256::from(self.effective_gas_price) + self.l1_fee.unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -27,6 +47,7 @@ pub struct TransactionMonitor {
     poll_interval: Duration,
     timeout_block_number: u64,
     timeout_gas_used: U256,
+    subscriptions: Option<SubscriptionProvider>,
 }
 
 impl TransactionMonitor {
@@ -42,6 +63,7 @@ impl TransactionMonitor {
             poll_interval,
             timeout_block_number: 0,
             timeout_gas_used: U256::ZERO,
+            subscriptions: None,
         }
     }
 
@@ -58,55 +80,142 @@ impl TransactionMonitor {
             poll_interval,
             timeout_block_number,
             timeout_gas_used,
+            subscriptions: None,
         }
     }
 
+    // Receipts are then checked on every new head instead of every poll interval
+    pub fn with_subscriptions(mut self, subscriptions: Option<SubscriptionProvider>) -> Self {
+        self.subscriptions = subscriptions;
+        self
+    }
+
+    // Checks on every new head when subscribed, every poll interval otherwise. A dropped
+    // or failed subscription falls back to polling for the rest of the wait.
+    #[tracing::instrument(name = "transaction", skip(self), fields(tx_hash = %tx_hash))]
     pub async fn monitor_transaction(&self, tx_hash: B256) -> Result<TransactionReceipt> {
-        println!("🔍 Monitoring transaction: {:?}", tx_hash);
+        info!("🔍 Monitoring transaction: {:?}", tx_hash);
 
         let start_time = tokio::time::Instant::now();
         let overall_deadline = start_time + self.max_wait_time;
-        let mut ticker = tokio::time::interval(self.poll_interval);
+        let mut heads = match &self.subscriptions {
+            Some(subscriptions) => match subscriptions.subscribe_heads().await {
+                Ok(heads) => Some(heads),
+                Err(e) => {
+                    warn!("⚠️  newHeads unavailable, polling for the receipt: {}", e);
+                    None
+                }
+            },
+            None => None,
+        };
+        if let Some(receipt) = self.check_receipt(tx_hash, start_time).await {
+            return Ok(receipt);
+        }
+        let mut ticker =
+            tokio::time::interval_at(start_time + self.poll_interval, self.poll_interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
         loop {
             tokio::select! {
                 _ = tokio::time::sleep_until(overall_deadline) => {
-                    println!("⏰ Transaction monitoring timeout after {:?}", self.max_wait_time);
+                    info!("⏰ Transaction monitoring timeout after {:?}", self.max_wait_time);
+                    metrics()
+                        .tx_confirmation
+                        .with_label_values(&["timeout"])
+                        .observe(start_time.elapsed().as_secs_f64());
                     return Ok(TransactionReceipt {
                         hash: tx_hash,
                         block_number: self.timeout_block_number,
                         gas_used: self.timeout_gas_used,
                         status: TransactionStatus::Timeout,
+                        effective_gas_price: 0,
+                        l1_fee: None,
+                        estimated_cost_wei: None,
                     });
                 }
-                _ = ticker.tick() => {
-                    // Check transaction status
-                    match self.provider.get_transaction_receipt(tx_hash).await {
-                        Ok(Some(receipt)) => {
-                            let status = if receipt.status() {
-                                TransactionStatus::Success
-                            } else {
-                                TransactionStatus::Failed
-                            };
-
-                            println!("✅ Transaction confirmed: {:?} (Status: {:?})", tx_hash, status);
-
-                            return Ok(TransactionReceipt {
-                                hash: tx_hash,
-                                block_number: receipt.block_number.unwrap_or(0),
-                                gas_used: U256::from(receipt.gas_used),
-                                status,
-                            });
-                        }
-                        Ok(None) => {
-                            println!("⏳ Transaction pending, waiting...");
-                        }
-                        Err(e) => {
-                            println!("❌ Error checking transaction status: {}", e);
-                        }
+                _ = ticker.tick(), if heads.is_none() => {}
+                head = next_head(&mut heads) => {
+                    if let Err(RecvError::Closed) = head {
+                        warn!("⚠️  newHeads subscription dropped, polling for the receipt");
+                        heads = None;
                     }
                 }
             }
+            if let Some(receipt) = self.check_receipt(tx_hash, start_time).await {
+                return Ok(receipt);
+            }
         }
     }
+
+    async fn check_receipt(
+        &self,
+        tx_hash: B256,
+        start_time: tokio::time::Instant,
+    ) -> Option<TransactionReceipt> {
+        // Raw request so OP-stack fields like `l1Fee` survive deserialization
+        let receipt = self
+            .provider
+            .client()
+            .request::<_, Option<WithOtherFields<alloy::rpc::types::TransactionReceipt>>>(
+                "eth_getTransactionReceipt",
+                (tx_hash,),
+            )
+            .await;
+        match receipt {
+            Ok(Some(receipt)) => {
+                let status = if receipt.status() {
+                    TransactionStatus::Success
+                } else {
+                    TransactionStatus::Failed
+                };
+
+                info!(
+                    "✅ Transaction confirmed: {:?} (Status: {:?})",
+                    tx_hash, status
+                );
+                let label = if status == TransactionStatus::Success {
+                    "success"
+                } else {
+                    "failed"
+                };
+                metrics()
+                    .tx_confirmation
+                    .with_label_values(&[label])
+                    .observe(start_time.elapsed().as_secs_f64());
+                metrics()
+                    .tx_gas_used
+                    .with_label_values(&[label])
+                    .observe(receipt.gas_used as f64);
+
+                Some(TransactionReceipt {
+                    hash: tx_hash,
+                    block_number: receipt.block_number.unwrap_or(0),
+                    gas_used: U256::from(receipt.gas_used),
+                    status,
+                    effective_gas_price: receipt.effective_gas_price,
+                    l1_fee: receipt
+                        .other
+                        .get_deserialized::<U256>("l1Fee")
+                        .and_then(|fee| fee.ok()),
+                    estimated_cost_wei: None,
+                })
+            }
+            Ok(None) => {
+                info!("⏳ Transaction pending, waiting...");
+                None
+            }
+            Err(e) => {
+                error!("❌ Error checking transaction status: {}", e);
+                None
+            }
+        }
+    }
+}
+
+// Never resolves without a subscription, leaving the ticker in charge
+async fn next_head(heads: &mut Option<Subscription<Header>>) -> Result<Header, RecvError> {
+    match heads {
+        Some(heads) => heads.recv().await,
+        None => std::future::pending().await,
+    }
 }
@@ -3,16 +3,35 @@
 //! Tests for component interaction, KMS integration, blockchain connectivity, and end-to-end workflows.
 //! These tests verify that different components work together correctly.
 
-use alloy::primitives::Address;
+use alloy::network::EthereumWallet;
+use alloy::primitives::B256;
+use alloy::primitives::{Address, Bytes, U256, U64};
+use alloy::providers::ProviderBuilder;
+use alloy::rpc::types::TransactionRequest;
+use alloy::signers::local::PrivateKeySigner;
+use alloy::transports::mock::Asserter;
 use anyhow::Result;
 use stablecoin_backend::blockchain::BlockchainClient;
 use stablecoin_backend::config::ChainConfig;
+use stablecoin_backend::config::{AlertSettings, BoostTokenLimit, WebhookFormat, WebhookSettings};
+use stablecoin_backend::contracts::erc20::ERC20Contract;
 use stablecoin_backend::contracts::reward_redistributor::RewardRedistributorContract;
 use stablecoin_backend::contracts::usdsc::USDSCContract;
-use stablecoin_backend::jobs::{ClaimYieldJob, DistributeRewardsJob};
+use stablecoin_backend::jobs::boost_rewards::{
+    check_daily_total, payouts_today, CampaignConfig, CampaignConfigSource, CampaignStatus,
+};
+use stablecoin_backend::jobs::boost_rewards_s3::BoostRewardsS3;
+use stablecoin_backend::jobs::{ClaimYieldJob, DistributeRewardsJob, JobContext, JobOutcome};
+use stablecoin_backend::journal::{JournalStatus, TxJournal};
+use stablecoin_backend::notifier::{Alert, AlertKind, Notifier};
+use stablecoin_backend::quorum::QuorumReader;
+use stablecoin_backend::report::RunRecorder;
 use stablecoin_backend::retry::{execute_with_retry, RetryConfig};
+use stablecoin_backend::rpc_pool::RpcPool;
+use stablecoin_backend::server::{self, KeeperStatus};
 use stablecoin_backend::transaction_monitor::TransactionMonitor;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 
 #[tokio::test]
@@ -413,3 +432,1198 @@ region = "us-east-1"
     std::fs::remove_file(&temp_file)?;
     Ok(config)
 }
+
+#[tokio::test]
+async fn test_job_context_with_mock_client() -> Result<()> {
+    // Test that jobs run against an injected client without KMS or a live RPC
+    let config = create_test_config()?;
+    let asserter = Asserter::new();
+    let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+    let client = BlockchainClient::from_provider(Arc::new(provider), Address::ZERO);
+    let ctx = JobContext::from_client(&config, client)?;
+
+    // yield() returns less than min_yield_threshold (1000000), so the claim is skipped
+    asserter.push_success(&Bytes::from(
+        U256::from(999_999).to_be_bytes::<32>().to_vec(),
+    ));
+    let job = ClaimYieldJob::new(config, false);
+    job.execute(&ctx).await?;
+
+    println!("✅ Job context with mock client test passed");
+    Ok(())
+}
+
+struct StaticCampaigns(Vec<CampaignConfig>);
+
+#[async_trait::async_trait]
+impl CampaignConfigSource for StaticCampaigns {
+    async fn get_campaigns(&self) -> Result<Vec<CampaignConfig>> {
+        Ok(self.0.clone())
+    }
+}
+
+#[tokio::test]
+async fn test_boost_daily_cap_spans_campaigns_and_journal() -> Result<()> {
+    let token = Address::repeat_byte(0x7e);
+    let mut config = create_test_config()?;
+    config.boost_tokens = Some(vec![BoostTokenLimit {
+        address: format!("{:?}", token),
+        max_daily_amount: 100.0,
+        max_campaign_amount: 10_000.0,
+    }]);
+    let path = std::env::temp_dir().join(format!("boost_cap_test_{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let journal = Arc::new(TxJournal::open(&path)?);
+
+    let asserter = Asserter::new();
+    let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+    let client = BlockchainClient::from_provider(Arc::new(provider), Address::ZERO)
+        .with_journal(journal.clone());
+    let ctx = JobContext::from_client(&config, client)?;
+
+    // 60/day on its own is under the cap
+    let today = chrono::Utc::now().date_naive();
+    let campaign = |id: &str| CampaignConfig {
+        id: id.to_string(),
+        token_address: token,
+        total_amount: 600.0,
+        start_date: today - chrono::Duration::days(5),
+        end_date: today + chrono::Duration::days(4),
+        status: CampaignStatus::Active,
+    };
+
+    // Two campaigns paying the same token add up to 120/day: refused before any send
+    let job = BoostRewardsS3::new(
+        config.clone(),
+        Box::new(StaticCampaigns(vec![campaign("a"), campaign("b")])),
+    );
+    let err = job.run(&ctx).await.unwrap_err();
+    assert!(err.to_string().contains("max_daily_amount"), "{}", err);
+
+    // 50 (6 decimals) already journaled today by another campaign, plus 60 planned
+    let id = journal.record_intent(
+        Some("boost-rewards"),
+        &ERC20Contract::transfer_intent("other", token, Address::ZERO, U256::from(50_000_000u64)),
+        0,
+    )?;
+    journal.record_status(&id, JournalStatus::Confirmed)?;
+    asserter.push_success(&Bytes::from(U256::from(6).to_be_bytes::<32>().to_vec()));
+    let job = BoostRewardsS3::new(
+        config.clone(),
+        Box::new(StaticCampaigns(vec![campaign("a")])),
+    );
+    let err = job.run(&ctx).await.unwrap_err();
+    assert!(err.to_string().contains("50 already sent"), "{}", err);
+
+    // A manual run goes through the same journal-backed check
+    asserter.push_success(&Bytes::from(U256::from(6).to_be_bytes::<32>().to_vec()));
+    let (_, sent_today) = payouts_today(ctx.client(), token).await?;
+    assert_eq!(sent_today, 50.0);
+    let limit = &config.boost_tokens.as_ref().unwrap()[0];
+    assert!(check_daily_total(limit, token, sent_today, 40.0, "campaign manual").is_ok());
+    let err = check_daily_total(limit, token, sent_today, 60.0, "campaign manual").unwrap_err();
+    assert!(
+        err.to_string().contains("planned by campaign manual"),
+        "{}",
+        err
+    );
+
+    // Without a journal earlier payouts can't be counted, so the cap fails closed
+    let provider = ProviderBuilder::new().connect_mocked_client(Asserter::new());
+    let client = BlockchainClient::from_provider(Arc::new(provider), Address::ZERO);
+    let ctx = JobContext::from_client(&config, client)?;
+    let job = BoostRewardsS3::new(config, Box::new(StaticCampaigns(vec![campaign("a")])));
+    let err = job.run(&ctx).await.unwrap_err();
+    assert!(err.to_string().contains("journal"), "{}", err);
+
+    std::fs::remove_file(&path)?;
+    println!("✅ Boost daily cap test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transaction_journal_and_reconcile() -> Result<()> {
+    let config = create_test_config()?;
+    let path = std::env::temp_dir().join(format!("journal_test_{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let journal = Arc::new(TxJournal::open(&path)?);
+
+    // Signed locally, so the raw bytes are journaled before the broadcast
+    let signer = PrivateKeySigner::random();
+    let keeper = signer.address();
+    let asserter = Asserter::new();
+    let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+    let client = BlockchainClient::from_provider(Arc::new(provider), keeper)
+        .with_wallet(EthereumWallet::from(signer), 1)
+        .with_journal(journal.clone());
+
+    asserter.push_success(&B256::ZERO);
+    let tx = TransactionRequest {
+        to: Some(Address::ZERO.into()),
+        nonce: Some(0),
+        gas: Some(21_000),
+        max_fee_per_gas: Some(2_000_000_000),
+        max_priority_fee_per_gas: Some(1_000_000_000),
+        ..Default::default()
+    };
+    let tx_hash = client.send_transaction("claimYield", tx).await?;
+
+    let entries = journal.entries()?;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].intent, "claimYield");
+    assert_eq!(entries[0].status, JournalStatus::Sent);
+    assert_eq!(entries[0].hash, Some(tx_hash));
+    assert!(entries[0]
+        .raw_tx
+        .as_deref()
+        .is_some_and(|raw| raw.starts_with("0x02")));
+
+    // A crash before signing leaves a bare intent, and a torn last line is ignored
+    journal.record_intent(None, "distribute", 1)?;
+    std::fs::write(&path, std::fs::read_to_string(&path)? + "{\"id\": \"torn")?;
+    assert_eq!(journal.unfinished()?.len(), 2);
+
+    // Next run: nonce 0 was mined by another tx and the receipt is missing, so the
+    // sent tx is dropped; the bare intent was never signed
+    let ctx = JobContext::from_client(&config, client)?;
+    asserter.push_success(&U64::from(1));
+    asserter.push_success(&Option::<()>::None);
+    ctx.reconcile_journal().await?;
+
+    let entries = journal.entries()?;
+    assert!(entries
+        .iter()
+        .all(|entry| entry.status == JournalStatus::Dropped));
+    assert_eq!(
+        entries[0].error.as_deref(),
+        Some("nonce used by another transaction")
+    );
+    assert_eq!(entries[1].error.as_deref(), Some("never signed"));
+
+    // Once per context: a later job's intent mid-send is left alone (no RPC is made)
+    journal.record_intent(None, "claimYield", 2)?;
+    ctx.reconcile_journal().await?;
+    assert_eq!(journal.unfinished()?.len(), 1);
+    std::fs::remove_file(&path)?;
+
+    println!("✅ Transaction journal test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_run_report_records_decision() -> Result<()> {
+    let mut config = create_test_config()?;
+    let report_dir = std::env::temp_dir().join(format!("run_reports_{}", std::process::id()));
+    config.report.dir = Some(report_dir.to_string_lossy().to_string());
+
+    let asserter = Asserter::new();
+    let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+    let client = BlockchainClient::from_provider(Arc::new(provider), Address::ZERO);
+    let ctx = JobContext::from_client(&config, client)?;
+
+    asserter.push_success(&Bytes::from(
+        U256::from(999_999).to_be_bytes::<32>().to_vec(),
+    ));
+    let recorder = RunRecorder::new("claim-yield", &config, false);
+    let result = ClaimYieldJob::new(config.clone(), false)
+        .with_report(recorder.clone())
+        .execute(&ctx)
+        .await;
+    let report = recorder.finish(&result);
+    assert_eq!(result?, JobOutcome::NoOp);
+
+    assert_eq!(report.outcome, "success");
+    assert_eq!(report.decisions, vec!["skipped: yield below threshold"]);
+    assert_eq!(report.reads["pending_yield"], "999999");
+    assert_eq!(report.reads["min_yield_threshold"], "1000000");
+    assert!(report.transactions.is_empty());
+
+    // Written as <timestamp>-<chain id>-<job>.json under the configured dir
+    report.write(&config).await;
+    let written = std::fs::read_to_string(report_dir.join(report.file_name()))?;
+    let json: serde_json::Value = serde_json::from_str(&written)?;
+    assert_eq!(json["job"], "claim-yield");
+    assert_eq!(json["config_hash"], report.config_hash);
+    std::fs::remove_dir_all(&report_dir)?;
+
+    println!("✅ Run report test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_keeper_status_endpoints() -> Result<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let config = create_test_config()?;
+    let asserter = Asserter::new();
+    let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+    let client = BlockchainClient::from_provider(Arc::new(provider), Address::ZERO);
+    let ctx = Arc::new(JobContext::from_client(&config, client)?);
+    let status = KeeperStatus::new();
+
+    // Not ready until a chain is connected
+    assert!(!status.readiness().await.0);
+    status.register_context(ctx.clone());
+    status.register_context(ctx);
+
+    // Ready when the RPC reports the configured chain id, not ready on a mismatch
+    asserter.push_success(&U64::from(1));
+    let (ready, checks) = status.readiness().await;
+    assert!(ready);
+    assert_eq!(checks.len(), 1);
+    asserter.push_success(&U64::from(5));
+    assert!(!status.readiness().await.0);
+
+    // Status reports the last run per job and the keeper balance
+    status.run_started("claim-yield");
+    status.run_finished("claim-yield", &Ok(()));
+    status.run_started("distribute-rewards");
+    status.run_finished::<()>("distribute-rewards", &Err(anyhow::anyhow!("boom")));
+    asserter.push_success(&U256::from(1_500_000_000_000_000_000u128));
+    let report = status.status().await;
+    assert_eq!(report["jobs"][0]["job"], "claim-yield");
+    assert_eq!(report["jobs"][0]["outcome"], "success");
+    assert_eq!(report["jobs"][1]["outcome"], "failure");
+    assert_eq!(report["jobs"][1]["error"], "boom");
+    assert_eq!(
+        report["chains"][0]["gas_balance_eth"],
+        "1.500000000000000000"
+    );
+
+    // The embedded server answers liveness probes
+    let (addr, handle) = server::spawn("127.0.0.1:0".parse()?, status).await?;
+    let mut stream = tokio::net::TcpStream::connect(addr).await?;
+    stream
+        .write_all(b"GET /healthz HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await?;
+    assert!(response.starts_with("HTTP/1.1 200"));
+    assert!(response.ends_with("ok"));
+    handle.abort();
+
+    println!("✅ Keeper status endpoints test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_notifier_webhook_dedup_and_rate_limit() -> Result<()> {
+    use axum::routing::post;
+    use axum::Json;
+    use std::sync::Mutex;
+
+    // Local webhook receiver recording every payload
+    let received: Arc<Mutex<Vec<serde_json::Value>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = received.clone();
+    let app = axum::Router::new().route(
+        "/hook",
+        post(move |Json(body): Json<serde_json::Value>| {
+            let sink = sink.clone();
+            async move {
+                sink.lock().unwrap().push(body);
+                "ok"
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}/hook", listener.local_addr()?);
+    let server = tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let settings = AlertSettings {
+        webhooks: vec![WebhookSettings {
+            url,
+            format: WebhookFormat::Slack,
+        }],
+        max_alerts_per_hour: 2,
+        ..Default::default()
+    };
+    let state_path = std::env::temp_dir().join(format!("alerts_test_{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&state_path);
+    let notifier = Notifier::new(settings.clone()).with_state_file(&state_path);
+    let alert = |subject: &str| {
+        Alert::new(
+            AlertKind::JobFailed,
+            1946,
+            subject,
+            format!("{} failed: boom", subject),
+        )
+    };
+
+    // Duplicates within the window are suppressed, then the hourly limit kicks in
+    assert!(notifier.notify(alert("distribute-rewards")).await);
+    assert!(!notifier.notify(alert("distribute-rewards")).await);
+    assert!(notifier.notify(alert("claim-yield")).await);
+    assert!(!notifier.notify(alert("boost-rewards-s3")).await);
+
+    let received = received.lock().unwrap().clone();
+    assert_eq!(received.len(), 2);
+    let text = received[0]["text"].as_str().unwrap();
+    assert!(text.contains("job_failed"));
+    assert!(text.contains("distribute-rewards failed: boom"));
+
+    // The next one-shot run starts a new notifier but reads the same state, so the
+    // hourly limit still holds
+    let next_run = Notifier::new(settings).with_state_file(&state_path);
+    assert!(!next_run.notify(alert("liveness")).await);
+    std::fs::remove_file(&state_path)?;
+
+    // Without webhooks nothing is sent
+    let disabled = Notifier::new(AlertSettings::default());
+    assert!(!disabled.notify(alert("claim-yield")).await);
+
+    server.abort();
+    println!("✅ Notifier webhook dedup and rate limit test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rpc_pool_failover_and_lag_demotion() -> Result<()> {
+    use alloy::providers::Provider;
+    use axum::http::StatusCode;
+    use axum::routing::post;
+    use axum::Json;
+
+    // Local JSON-RPC node answering every call with a fixed block number, or a 429
+    async fn mock_node(block: Option<u64>) -> Result<String> {
+        let app = axum::Router::new().route(
+            "/",
+            post(move |Json(body): Json<serde_json::Value>| async move {
+                match block {
+                    Some(block) => (
+                        StatusCode::OK,
+                        Json(serde_json::json!({
+                            "jsonrpc": "2.0",
+                            "id": body["id"],
+                            "result": format!("{:#x}", block),
+                        })),
+                    ),
+                    None => (
+                        StatusCode::TOO_MANY_REQUESTS,
+                        Json(serde_json::json!({ "error": "rate limited" })),
+                    ),
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}/", listener.local_addr()?);
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        Ok(url)
+    }
+
+    let limited = mock_node(None).await?;
+    let behind = mock_node(Some(100)).await?;
+    let head = mock_node(Some(200)).await?;
+    let settings: stablecoin_backend::config::ChainSettings = toml::from_str(&format!(
+        r#"
+chain_id = 1946
+rpc_url = "{}"
+max_block_lag = 5
+rpc_endpoints = [
+    {{ url = "{}", priority = 1 }},
+    {{ url = "{}", priority = 2 }},
+]
+"#,
+        limited, behind, head
+    ))?;
+    let pool = RpcPool::new(&settings)?;
+    assert_eq!(pool.len(), 3);
+    let provider = ProviderBuilder::new().connect_client(pool.client());
+
+    // The rate limited primary fails over to the next priority
+    assert_eq!(provider.get_block_number().await?, 100);
+    let health = pool.health();
+    assert!(!health[0].healthy);
+    assert_eq!(health[0].consecutive_failures, 1);
+    assert!(health[1].healthy);
+
+    // The health check demotes the lagging endpoint below the one at the head
+    pool.check_health().await;
+    let health = pool.health();
+    assert!(health[1].lagging);
+    assert_eq!(health[1].block, Some(100));
+    assert!(health[2].healthy);
+    assert_eq!(provider.get_block_number().await?, 200);
+
+    println!("✅ RPC pool failover and lag demotion test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_quorum_reads_abort_on_disagreement() -> Result<()> {
+    use alloy::network::Ethereum;
+    use alloy::providers::Provider;
+    use axum::routing::post;
+    use axum::Json;
+    use stablecoin_backend::error::{self, KeeperError};
+
+    // Local node at `head` answering eth_call with `value`, but only when pinned to block 100
+    async fn mock_node(head: u64, value: u64) -> Result<Arc<dyn Provider<Ethereum>>> {
+        let app = axum::Router::new().route(
+            "/",
+            post(move |Json(body): Json<serde_json::Value>| async move {
+                let result = match body["method"].as_str() {
+                    Some("eth_blockNumber") => serde_json::json!(format!("{:#x}", head)),
+                    Some("eth_call") if body["params"][1] == "0x64" => {
+                        serde_json::json!(format!("0x{:064x}", value))
+                    }
+                    _ => {
+                        return Json(serde_json::json!({
+                            "jsonrpc": "2.0",
+                            "id": body["id"],
+                            "error": { "code": -32000, "message": "unexpected request" },
+                        }))
+                    }
+                };
+                Json(serde_json::json!({ "jsonrpc": "2.0", "id": body["id"], "result": result }))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}/", listener.local_addr()?).parse()?;
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        Ok(Arc::new(ProviderBuilder::new().connect_http(url)))
+    }
+
+    let usdsc = Address::from_str("0x1111111111111111111111111111111111111111")?;
+    let keeper = Address::from_str("0x2222222222222222222222222222222222222222")?;
+    let asserter = Asserter::new();
+    let provider = Arc::new(ProviderBuilder::new().connect_mocked_client(asserter.clone()));
+
+    // Agreeing endpoints are read at the lowest head among them
+    let quorum = QuorumReader::new(vec![
+        ("a".to_string(), mock_node(101, 5_000).await?),
+        ("b".to_string(), mock_node(100, 5_000).await?),
+    ]);
+    let client =
+        BlockchainClient::from_provider(provider.clone(), keeper).with_quorum(Arc::new(quorum));
+    let contract = USDSCContract::new(usdsc, provider.clone(), client);
+    assert_eq!(contract.get_pending_yield().await?, U256::from(5_000));
+
+    // One endpoint returning a different value aborts the read
+    let quorum = QuorumReader::new(vec![
+        ("a".to_string(), mock_node(100, 5_000).await?),
+        ("b".to_string(), mock_node(100, 9_000).await?),
+    ]);
+    let client =
+        BlockchainClient::from_provider(provider.clone(), keeper).with_quorum(Arc::new(quorum));
+    let contract = USDSCContract::new(usdsc, provider.clone(), client);
+    let err = contract.get_pending_yield().await.unwrap_err();
+    assert!(matches!(
+        KeeperError::find(&err),
+        Some(KeeperError::QuorumDisagreement(_))
+    ));
+    assert_eq!(error::exit_code(&err), error::EXIT_QUORUM_DISAGREEMENT);
+
+    println!("✅ Quorum reads test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_liveness_check_skips_stalled_chain() -> Result<()> {
+    use alloy::rpc::types::{Block, Header};
+    use stablecoin_backend::error;
+
+    let config = create_test_config()?;
+    let keeper = Address::from_str("0x2222222222222222222222222222222222222222")?;
+    let block_at = |number: u64, age_seconds: u64| {
+        let mut header: Header = Header::default();
+        header.inner.number = number;
+        header.inner.timestamp = chrono::Utc::now().timestamp() as u64 - age_seconds;
+        Block::<alloy::rpc::types::Transaction>::empty(header)
+    };
+
+    let asserter = Asserter::new();
+    let provider = Arc::new(ProviderBuilder::new().connect_mocked_client(asserter.clone()));
+    let ctx = JobContext::from_client(&config, BlockchainClient::from_provider(provider, keeper))?;
+
+    // A recent head passes
+    asserter.push_success(&block_at(1_000, 10));
+    assert_eq!(ctx.check_liveness().await?, None);
+
+    // A head older than max_block_age_seconds (default 300) skips the run
+    asserter.push_success(&block_at(1_000, 900));
+    let reason = ctx
+        .check_liveness()
+        .await?
+        .expect("stalled chain is reported");
+    assert!(reason.contains("latest block 1000"));
+    assert_eq!(
+        JobOutcome::ChainStalled.exit_code(),
+        error::EXIT_CHAIN_STALLED
+    );
+
+    println!("✅ Liveness check test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_op_stack_l1_fee() -> Result<()> {
+    use stablecoin_backend::fees::FeeStrategy;
+    use stablecoin_backend::transaction_monitor::TransactionStatus;
+
+    let keeper = Address::from_str("0x2222222222222222222222222222222222222222")?;
+    let asserter = Asserter::new();
+    let provider = Arc::new(ProviderBuilder::new().connect_mocked_client(asserter.clone()));
+
+    // Without the flag there is no L1 data fee to price
+    let client = BlockchainClient::from_provider(provider.clone(), keeper);
+    let tx = TransactionRequest::default()
+        .to(Address::from_str(
+            "0x1111111111111111111111111111111111111111",
+        )?)
+        .input(Bytes::from(vec![0x4e, 0x71, 0xd9, 0x2d]).into());
+    assert_eq!(client.estimate_l1_fee(&tx).await?, None);
+
+    // With it, GasPriceOracle.getL1Fee prices the unsigned transaction
+    let client = client.with_l1_fee_oracle();
+    asserter.push_success(&Bytes::from(U256::from(42_000u64).to_be_bytes::<32>()));
+    assert_eq!(
+        client.estimate_l1_fee(&tx).await?,
+        Some(U256::from(42_000u64))
+    );
+
+    // The receipt's l1Fee is part of the actual cost
+    let tx_hash = B256::from([7u8; 32]);
+    asserter.push_success(&serde_json::json!({
+        "transactionHash": tx_hash,
+        "transactionIndex": "0x0",
+        "blockHash": B256::from([8u8; 32]),
+        "blockNumber": "0x64",
+        "from": keeper,
+        "to": "0x1111111111111111111111111111111111111111",
+        "contractAddress": null,
+        "gasUsed": "0x5208",
+        "cumulativeGasUsed": "0x5208",
+        "effectiveGasPrice": "0x3b9aca00",
+        "status": "0x1",
+        "type": "0x2",
+        "logs": [],
+        "logsBloom": format!("0x{}", "00".repeat(256)),
+        "l1Fee": "0xa410",
+    }));
+    let monitor =
+        TransactionMonitor::new(provider, Duration::from_secs(30), Duration::from_millis(10));
+    let receipt = monitor.monitor_transaction(tx_hash).await?;
+    assert_eq!(receipt.status, TransactionStatus::Success);
+    assert_eq!(receipt.l1_fee, Some(U256::from(42_000u64)));
+    assert_eq!(
+        receipt.total_cost_wei(),
+        U256::from(21_000u64 * 1_000_000_000 + 42_000)
+    );
+
+    // A total fee cap makes room for the L1 data fee by lowering the L2 max fee
+    let mut settings = create_test_config()?.transaction;
+    settings.max_total_fee_eth = Some(0.001);
+    let strategy = FeeStrategy::from_settings(&settings);
+    let fitted = strategy.fit_total_fee(
+        100_000_000_000,
+        10_000,
+        200_000_000_000_000,
+        Some(50_000_000_000),
+    )?;
+    assert_eq!(fitted, 80_000_000_000);
+    assert!(strategy
+        .fit_total_fee(100_000_000_000, 10_000, 1_000_000_000_000_000, None)
+        .is_err());
+
+    // A fitted fee below the base fee could never be included, so nothing is sent
+    let err = strategy
+        .fit_total_fee(
+            100_000_000_000,
+            10_000,
+            200_000_000_000_000,
+            Some(90_000_000_000),
+        )
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("below the current base fee"),
+        "{}",
+        err
+    );
+
+    println!("✅ OP-stack L1 fee test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_wait_for_next_block() -> Result<()> {
+    use stablecoin_backend::error::{self, KeeperError};
+
+    let mut config = create_test_config()?;
+    config.chain.block_time_ms = 10;
+    config.chain.max_block_wait_seconds = 1;
+    let keeper = Address::from_str("0x2222222222222222222222222222222222222222")?;
+    let asserter = Asserter::new();
+    let provider = Arc::new(ProviderBuilder::new().connect_mocked_client(asserter.clone()));
+    let ctx = JobContext::from_client(&config, BlockchainClient::from_provider(provider, keeper))?;
+
+    // Polls every block time until the height moves
+    asserter.push_success(&U64::from(100));
+    asserter.push_success(&U64::from(100));
+    asserter.push_success(&U64::from(100));
+    asserter.push_success(&U64::from(101));
+    assert_eq!(ctx.wait_for_next_block().await?, 101);
+
+    // A chain that doesn't move fails after max_block_wait_seconds
+    for _ in 0..200 {
+        asserter.push_success(&U64::from(101));
+    }
+    let err = ctx.wait_for_next_block().await.unwrap_err();
+    assert!(matches!(KeeperError::find(&err), Some(KeeperError::Rpc(_))));
+    assert_eq!(error::exit_code(&err), error::EXIT_RPC);
+
+    println!("✅ Wait for next block test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_subscription_provider_falls_back_to_polling() -> Result<()> {
+    use stablecoin_backend::subscription_provider::SubscriptionProvider;
+    use stablecoin_backend::transaction_monitor::TransactionStatus;
+
+    let mut config = create_test_config()?;
+    config.chain.block_time_ms = 10;
+    // Nothing listens here, and an unset variable doesn't count as an IPC path
+    config.chain.ws_url = Some("ws://127.0.0.1:1".to_string());
+    config.chain.ipc_path = Some("${KEEPER_TEST_UNSET_IPC_PATH}".to_string());
+    let subscriptions =
+        SubscriptionProvider::from_settings(&config.chain)?.expect("ws_url is configured");
+    assert_eq!(subscriptions.label(), "127.0.0.1:1");
+
+    let keeper = Address::from_str("0x2222222222222222222222222222222222222222")?;
+    let asserter = Asserter::new();
+    let provider = Arc::new(ProviderBuilder::new().connect_mocked_client(asserter.clone()));
+    let client = BlockchainClient::from_provider(provider.clone(), keeper)
+        .with_subscription_provider(subscriptions.clone());
+    let ctx = JobContext::from_client(&config, client)?;
+
+    // Block waiting polls over HTTP when the socket can't be reached
+    asserter.push_success(&U64::from(100));
+    asserter.push_success(&U64::from(101));
+    assert_eq!(ctx.wait_for_next_block().await?, 101);
+
+    // So does receipt waiting
+    let tx_hash = B256::from([9u8; 32]);
+    asserter.push_success(&serde_json::Value::Null);
+    asserter.push_success(&serde_json::json!({
+        "transactionHash": tx_hash,
+        "transactionIndex": "0x0",
+        "blockHash": B256::from([8u8; 32]),
+        "blockNumber": "0x66",
+        "from": keeper,
+        "to": "0x1111111111111111111111111111111111111111",
+        "contractAddress": null,
+        "gasUsed": "0x5208",
+        "cumulativeGasUsed": "0x5208",
+        "effectiveGasPrice": "0x3b9aca00",
+        "status": "0x1",
+        "type": "0x2",
+        "logs": [],
+        "logsBloom": format!("0x{}", "00".repeat(256)),
+    }));
+    let monitor =
+        TransactionMonitor::new(provider, Duration::from_secs(30), Duration::from_millis(10))
+            .with_subscriptions(Some(subscriptions));
+    let receipt = monitor.monitor_transaction(tx_hash).await?;
+    assert_eq!(receipt.status, TransactionStatus::Success);
+    assert_eq!(receipt.block_number, 0x66);
+
+    println!("✅ Subscription fallback test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_private_relay_submission_and_public_fallback() -> Result<()> {
+    use axum::http::HeaderMap;
+    use axum::routing::{get, post};
+    use axum::Json;
+    use stablecoin_backend::config::{PrivateRelaySettings, RelayMethod};
+    use stablecoin_backend::transaction_monitor::TransactionStatus;
+    use std::sync::Mutex;
+
+    // Local relay recording every call and reporting every transaction as pending
+    let calls: Arc<Mutex<Vec<(serde_json::Value, bool)>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = calls.clone();
+    let app = axum::Router::new()
+        .route(
+            "/",
+            post(
+                move |headers: HeaderMap, Json(body): Json<serde_json::Value>| {
+                    let sink = sink.clone();
+                    async move {
+                        let signed = headers
+                            .get("X-Flashbots-Signature")
+                            .and_then(|value| value.to_str().ok())
+                            .is_some_and(|value| value.starts_with("0x") && value.contains(':'));
+                        let result = match body["method"].as_str() {
+                            Some("eth_sendBundle") => {
+                                serde_json::json!({ "bundleHash": B256::from([3u8; 32]) })
+                            }
+                            _ => serde_json::json!(B256::from([4u8; 32])),
+                        };
+                        let id = body["id"].clone();
+                        sink.lock().unwrap().push((body, signed));
+                        Json(serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+                    }
+                },
+            ),
+        )
+        .route(
+            "/tx/{hash}",
+            get(|| async { Json(serde_json::json!({ "status": "PENDING" })) }),
+        );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let relay_url = format!("http://{}", listener.local_addr()?);
+    let server = tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let receipt_json = |tx_hash: B256, from: Address| {
+        serde_json::json!({
+            "transactionHash": tx_hash,
+            "transactionIndex": "0x0",
+            "blockHash": B256::from([8u8; 32]),
+            "blockNumber": "0x67",
+            "from": from,
+            "to": Address::ZERO,
+            "contractAddress": null,
+            "gasUsed": "0x5208",
+            "cumulativeGasUsed": "0x5208",
+            "effectiveGasPrice": "0x3b9aca00",
+            "status": "0x1",
+            "type": "0x2",
+            "logs": [],
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+        })
+    };
+
+    for method in [RelayMethod::PrivateTransaction, RelayMethod::Bundle] {
+        calls.lock().unwrap().clear();
+        let mut config = create_test_config()?;
+        config.chain.block_time_ms = 10;
+        config.private_relay = Some(PrivateRelaySettings {
+            url: format!("{}/", relay_url),
+            method,
+            jobs: vec!["claim-yield".to_string()],
+            max_blocks: 2,
+            status_url: Some(format!("{}/tx", relay_url)),
+            auth_key: None,
+        });
+
+        let signer = PrivateKeySigner::random();
+        let keeper = signer.address();
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        let client = BlockchainClient::from_provider(Arc::new(provider), keeper)
+            .with_wallet(EthereumWallet::from(signer), 1);
+        let ctx = JobContext::from_client(&config, client)?;
+
+        // Only selected jobs go through the relay: the head is read, nothing is broadcast
+        asserter.push_success(&U64::from(100));
+        let tx = TransactionRequest {
+            to: Some(Address::ZERO.into()),
+            nonce: Some(0),
+            gas: Some(21_000),
+            max_fee_per_gas: Some(2_000_000_000),
+            max_priority_fee_per_gas: Some(1_000_000_000),
+            ..Default::default()
+        };
+        let tx_hash = ctx
+            .client_for("claim-yield")
+            .send_transaction("claimYield", tx)
+            .await?;
+
+        // Not mined within max_blocks, so the same signed tx goes public, then its
+        // receipt is awaited as usual
+        asserter.push_success(&U64::from(101));
+        asserter.push_success(&serde_json::Value::Null);
+        asserter.push_success(&U64::from(102));
+        asserter.push_success(&serde_json::Value::Null);
+        asserter.push_success(&tx_hash);
+        asserter.push_success(&receipt_json(tx_hash, keeper));
+        let receipt = ctx.monitor_transaction("Claim yield", tx_hash).await?;
+        assert_eq!(receipt.status, TransactionStatus::Success);
+
+        let calls = calls.lock().unwrap().clone();
+        assert!(calls.iter().all(|(_, signed)| *signed));
+        match method {
+            RelayMethod::PrivateTransaction => {
+                assert_eq!(calls.len(), 1);
+                assert_eq!(calls[0].0["method"], "eth_sendPrivateTransaction");
+                assert_eq!(calls[0].0["params"][0]["maxBlockNumber"], "0x66");
+            }
+            // Sent for block 101, then again for 102 once 101 passed without it
+            RelayMethod::Bundle => {
+                assert_eq!(calls.len(), 2);
+                assert_eq!(calls[0].0["method"], "eth_sendBundle");
+                assert_eq!(calls[0].0["params"][0]["blockNumber"], "0x65");
+                assert_eq!(calls[1].0["params"][0]["blockNumber"], "0x66");
+            }
+        }
+    }
+
+    // A tx the relay never landed is rebroadcast through the relay by the next run's
+    // reconcile, keeping the public fallback after max_blocks
+    calls.lock().unwrap().clear();
+    let mut config = create_test_config()?;
+    config.chain.block_time_ms = 10;
+    config.private_relay = Some(PrivateRelaySettings {
+        url: format!("{}/", relay_url),
+        method: RelayMethod::PrivateTransaction,
+        jobs: vec!["claim-yield".to_string()],
+        max_blocks: 2,
+        status_url: Some(format!("{}/tx", relay_url)),
+        auth_key: None,
+    });
+    let path = std::env::temp_dir().join(format!("relay_journal_{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let journal = Arc::new(TxJournal::open(&path)?);
+    let signer = PrivateKeySigner::random();
+    let keeper = signer.address();
+    let asserter = Asserter::new();
+    let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+    let client = BlockchainClient::from_provider(Arc::new(provider), keeper)
+        .with_wallet(EthereumWallet::from(signer), 1)
+        .with_journal(journal.clone());
+
+    asserter.push_success(&U64::from(100));
+    let tx = TransactionRequest {
+        to: Some(Address::ZERO.into()),
+        nonce: Some(0),
+        gas: Some(21_000),
+        max_fee_per_gas: Some(2_000_000_000),
+        max_priority_fee_per_gas: Some(1_000_000_000),
+        ..Default::default()
+    };
+    let tx_hash = JobContext::from_client(&config, client.clone())?
+        .client_for("claim-yield")
+        .send_transaction("claimYield", tx)
+        .await?;
+    assert_eq!(journal.entries()?[0].job.as_deref(), Some("claim-yield"));
+
+    // Next run: no receipt and the nonce is still free, so it goes back to the relay
+    let ctx = JobContext::from_client(&config, client)?;
+    asserter.push_success(&U64::from(0));
+    asserter.push_success(&serde_json::Value::Null);
+    asserter.push_success(&U64::from(200));
+    asserter.push_success(&U64::from(201));
+    asserter.push_success(&serde_json::Value::Null);
+    asserter.push_success(&U64::from(202));
+    asserter.push_success(&serde_json::Value::Null);
+    asserter.push_success(&tx_hash);
+    asserter.push_success(&receipt_json(tx_hash, keeper));
+    ctx.reconcile_journal().await?;
+
+    let calls = calls.lock().unwrap().clone();
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[1].0["method"], "eth_sendPrivateTransaction");
+    assert_eq!(calls[1].0["params"][0]["maxBlockNumber"], "0xca");
+    assert_eq!(journal.entries()?[0].status, JournalStatus::Confirmed);
+    std::fs::remove_file(&path)?;
+
+    server.abort();
+    println!("✅ Private relay test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_presigned_batch_and_aggregate3() -> Result<()> {
+    use alloy::sol_types::SolCall;
+    use stablecoin_backend::config::DistributeSubmission;
+    use stablecoin_backend::contracts::multicall3::{
+        IMulticall3, Multicall3Contract, MULTICALL3_ADDRESS,
+    };
+    use stablecoin_backend::contracts::reward_redistributor::TxOverrides;
+    use stablecoin_backend::error::KeeperError;
+
+    let signer = PrivateKeySigner::random();
+    let keeper = signer.address();
+    let asserter = Asserter::new();
+    let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+    let client = BlockchainClient::from_provider(Arc::new(provider), keeper)
+        .with_wallet(EthereumWallet::from(signer), 1);
+    let tx = |gas: u64| TransactionRequest {
+        to: Some(Address::ZERO.into()),
+        gas: Some(gas),
+        max_fee_per_gas: Some(2_000_000_000),
+        max_priority_fee_per_gas: Some(1_000_000_000),
+        ..Default::default()
+    };
+
+    // One pending-count read, then both signed before the first broadcast
+    asserter.push_success(&U64::from(5));
+    asserter.push_success(&B256::from([1u8; 32]));
+    asserter.push_success(&B256::from([2u8; 32]));
+    let hashes = client
+        .send_presigned(vec![
+            ("snapshotVaultTVLs", tx(100_000)),
+            ("distribute", tx(500_000)),
+        ])
+        .await?;
+    assert_eq!(hashes.len(), 2);
+    assert_ne!(hashes[0], hashes[1]);
+    let recent = client.recent_transactions();
+    assert_eq!(
+        recent.iter().map(|sent| sent.nonce).collect::<Vec<_>>(),
+        vec![5, 6]
+    );
+
+    // The node refuses the first: the second is never sent and both nonces go back,
+    // so the next allocation reuses 7 without asking the node
+    asserter.push_failure_msg("nonce too low");
+    assert!(client
+        .send_presigned(vec![
+            ("snapshotVaultTVLs", tx(100_000)),
+            ("distribute", tx(500_000))
+        ])
+        .await
+        .is_err());
+    asserter.push_success(&B256::from([3u8; 32]));
+    client.send_transaction("claimYield", tx(21_000)).await?;
+    assert_eq!(client.recent_transactions().last().unwrap().nonce, 7);
+
+    // A send refused below a nonce another job still holds gets its gap filled by a
+    // 0-value self-transfer, so the later one isn't stuck if the run ends here
+    assert_eq!(client.nonce_manager().next_nonces(2).await?, vec![8, 9]);
+    assert!(!client.nonce_manager().release(8).await);
+    asserter.push_failure_msg("insufficient funds for gas * price + value");
+    asserter.push_success(&serde_json::json!({
+        "oldestBlock": "0x1",
+        "baseFeePerGas": ["0x3b9aca00", "0x3b9aca00"],
+        "gasUsedRatio": [0.5],
+        "reward": [["0x3b9aca00"]],
+    }));
+    asserter.push_success(&B256::from([4u8; 32]));
+    assert!(client
+        .send_transaction("claimYield", tx(21_000))
+        .await
+        .is_err());
+    assert_eq!(client.recent_transactions().last().unwrap().nonce, 8);
+    assert_eq!(client.nonce_manager().next_nonce().await?, 10);
+
+    // presign lets the caller wait between sends, e.g. for the snapshot's receipt; what it
+    // decides not to send goes back
+    let mut batch = client
+        .presign(vec![
+            ("snapshotVaultTVLs", tx(100_000)),
+            ("distribute", tx(500_000)),
+        ])
+        .await?;
+    asserter.push_success(&B256::from([5u8; 32]));
+    client.send_next_presigned(&mut batch).await?;
+    assert_eq!(client.recent_transactions().last().unwrap().nonce, 11);
+    client
+        .discard_presigned(batch, &anyhow::anyhow!("snapshot reverted"))
+        .await;
+    assert_eq!(client.nonce_manager().next_nonce().await?, 12);
+
+    // aggregate3 runs snapshotVaultTVLs then distribute, neither allowed to fail
+    let redistributor_address = Address::from([9u8; 20]);
+    let redistributor =
+        RewardRedistributorContract::new(redistributor_address, client.provider(), client.clone());
+    let multicall = Multicall3Contract::new(MULTICALL3_ADDRESS, client.clone());
+    let snapshot = redistributor.snapshot_vault_tvls_request("0", TxOverrides::default())?;
+    let distribute = redistributor.distribute_request("0", TxOverrides::default())?;
+    let request = multicall.aggregate3_request(&[snapshot.clone(), distribute.clone()])?;
+    assert_eq!(request.to, Some(MULTICALL3_ADDRESS.into()));
+    let decoded = IMulticall3::aggregate3Call::abi_decode(request.input.input().unwrap())?;
+    assert_eq!(decoded.calls.len(), 2);
+    assert!(decoded
+        .calls
+        .iter()
+        .all(|call| call.target == redistributor_address && !call.allowFailure));
+    assert_eq!(&decoded.calls[0].callData, snapshot.input.input().unwrap());
+    assert_eq!(
+        &decoded.calls[1].callData,
+        distribute.input.input().unwrap()
+    );
+
+    // aggregate3 can't forward value
+    let paid = redistributor.distribute_request("1", TxOverrides::default())?;
+    assert!(multicall.aggregate3_request(&[paid]).is_err());
+
+    // multicall submission needs a private executor: unset or the public Multicall3 fails
+    // the run before anything is read or sent
+    let mut config = create_test_config()?;
+    config.distribute.submission = DistributeSubmission::Multicall;
+    let ctx = JobContext::from_client(&config, client)?;
+    for executor in [None, Some(format!("{:?}", MULTICALL3_ADDRESS))] {
+        config.distribute.executor_address = executor;
+        let err = DistributeRewardsJob::new(config.clone(), false)
+            .execute(&ctx)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<KeeperError>(),
+            Some(KeeperError::Config(_))
+        ));
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_batched_reads_through_multicall3() -> Result<()> {
+    use alloy::sol_types::SolCall;
+    use stablecoin_backend::contracts::erc20::{ERC20Contract, IERC20};
+    use stablecoin_backend::contracts::multicall3::{
+        BatchedReads, IMulticall3, MulticallUnavailable,
+    };
+
+    let asserter = Asserter::new();
+    let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+    let client = BlockchainClient::from_provider(Arc::new(provider), Address::ZERO);
+    let ok = |data: Vec<u8>| IMulticall3::Result {
+        success: true,
+        returnData: data.into(),
+    };
+    let aggregate = |results: Vec<IMulticall3::Result>| {
+        Bytes::from(IMulticall3::aggregate3Call::abi_encode_returns(&results))
+    };
+    let uint = |value: u64| U256::from(value).to_be_bytes::<32>().to_vec();
+
+    // Five redistributor getters plus the block number and timestamp, in one eth_call
+    let redistributor = RewardRedistributorContract::new(
+        Address::from([9u8; 20]),
+        client.provider(),
+        client.clone(),
+    );
+    asserter.push_success(&aggregate(vec![
+        ok(uint(100)),
+        ok(uint(1_700_000_000)),
+        ok(uint(90)),
+        ok(uint(3600)),
+        ok(uint(5_000)),
+        ok(uint(7_000)),
+        ok(uint(1_700_000_600)),
+    ]));
+    let state = redistributor.snapshot_state(Some(100)).await?;
+    assert_eq!(state.block_number, 100);
+    assert_eq!(state.last_snapshot_timestamp, U256::from(1_700_000_000u64));
+    assert_eq!(state.last_snapshot_block, U256::from(90));
+    assert_eq!(state.snapshot_max_age, U256::from(3600));
+    assert_eq!(state.last_susdsc_tvl, U256::from(5_000));
+    assert_eq!(state.last_earn_tvl, U256::from(7_000));
+    assert_eq!(state.block_timestamp, U256::from(1_700_000_600u64));
+
+    // ERC20 metadata and balance; a reverted slot fails only that read
+    let token = ERC20Contract::new(Address::from([7u8; 20]), client.provider(), client.clone());
+    let symbol = IERC20::symbolCall::abi_encode_returns(&"USDSC".to_string());
+    asserter.push_success(&aggregate(vec![
+        ok(uint(101)),
+        ok(uint(6)),
+        ok(symbol),
+        ok(uint(1_000_000)),
+    ]));
+    let token_state = token.token_state(Address::from([1u8; 20]), None).await?;
+    assert_eq!(token_state.decimals, 6);
+    assert_eq!(token_state.symbol, "USDSC");
+    assert_eq!(token_state.balance, U256::from(1_000_000));
+    assert_eq!(token_state.block_number, 101);
+
+    asserter.push_success(&aggregate(vec![
+        ok(uint(102)),
+        ok(uint(6)),
+        IMulticall3::Result {
+            success: false,
+            returnData: Bytes::new(),
+        },
+        ok(uint(1)),
+    ]));
+    assert!(token
+        .token_state(Address::from([1u8; 20]), None)
+        .await
+        .is_err());
+
+    // Pending yield folded into a caller's own batch
+    let usdsc = USDSCContract::new(Address::from([5u8; 20]), client.provider(), client.clone());
+    let mut reads = BatchedReads::new();
+    let pending_yield = usdsc.add_pending_yield(&mut reads);
+    let timestamp = reads.add_block_timestamp();
+    asserter.push_success(&aggregate(vec![
+        ok(uint(103)),
+        ok(uint(42)),
+        ok(uint(1_700_000_700)),
+    ]));
+    let results = reads
+        .execute(&client, &client.provider(), "pending yield", None)
+        .await?;
+    assert_eq!(results.block_number(), 103);
+    assert_eq!(results.get(&pending_yield)?, U256::from(42));
+    assert_eq!(results.get(&timestamp)?, U256::from(1_700_000_700u64));
+
+    // No Multicall3 deployed: the call returns nothing, the only case callers fall back on
+    asserter.push_success(&Bytes::new());
+    let err = redistributor.snapshot_state(None).await.unwrap_err();
+    assert!(err.downcast_ref::<MulticallUnavailable>().is_some());
+
+    // A failed read is a different error, passed on instead of read one by one
+    asserter.push_failure_msg("header not found");
+    let err = redistributor.snapshot_state(None).await.unwrap_err();
+    assert!(err.downcast_ref::<MulticallUnavailable>().is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_uncertain_send_resends_same_signed_tx() -> Result<()> {
+    use stablecoin_backend::error::KeeperError;
+
+    let path = std::env::temp_dir().join(format!("uncertain-send-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let journal = Arc::new(TxJournal::open(&path)?);
+    let signer = PrivateKeySigner::random();
+    let keeper = signer.address();
+    let asserter = Asserter::new();
+    let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+    let client = BlockchainClient::from_provider(Arc::new(provider), keeper)
+        .with_wallet(EthereumWallet::from(signer), 1)
+        .with_journal(journal.clone());
+    let tx = TransactionRequest {
+        to: Some(Address::ZERO.into()),
+        gas: Some(21_000),
+        max_fee_per_gas: Some(2_000_000_000),
+        max_priority_fee_per_gas: Some(1_000_000_000),
+        ..Default::default()
+    };
+
+    // The node's answer is lost (unreadable), then the resend is "already known": one
+    // signed tx, sent
+    asserter.push_success(&U64::from(3));
+    asserter.push_success(&"garbled");
+    asserter.push_failure_msg("already known");
+    let tx_hash = client.send_transaction("claimYield", tx.clone()).await?;
+    let entries = journal.entries()?;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].hash, Some(tx_hash));
+    assert_eq!(entries[0].status, JournalStatus::Sent);
+
+    // Never confirmed: a permanent error, the nonce stays taken and the entry stays
+    // signed for the reconcile; the retry loop doesn't sign a second tx
+    let attempts = std::sync::atomic::AtomicU32::new(0);
+    let result = execute_with_retry(
+        || {
+            attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            for _ in 0..3 {
+                asserter.push_success(&"garbled");
+            }
+            let client = client.clone();
+            let tx = tx.clone();
+            async move { client.send_transaction("claimYield", tx).await }
+        },
+        &RetryConfig::new(3, Duration::from_millis(1), Duration::from_millis(1), 1.0),
+        "Claim",
+    )
+    .await;
+    let error = result.unwrap_err();
+    assert!(matches!(
+        KeeperError::find(&error),
+        Some(KeeperError::SendUncertain(_))
+    ));
+    assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 1);
+    let entries = journal.entries()?;
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1].nonce, Some(4));
+    assert_eq!(entries[1].status, JournalStatus::Signed);
+
+    asserter.push_success(&B256::ZERO);
+    client.send_transaction("claimYield", tx).await?;
+    assert_eq!(journal.entries()?[2].nonce, Some(5));
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
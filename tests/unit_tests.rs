@@ -3,10 +3,25 @@
 //! Tests for individual functions, parsing logic, retry mechanisms, and data structures.
 //! These tests verify isolated functionality without external dependencies.
 
-use alloy::primitives::{B256, U256};
+use alloy::primitives::{Address, B256, U256, U64};
+use alloy::providers::ProviderBuilder;
+use alloy::transports::mock::Asserter;
 use anyhow::Result;
+use chrono::NaiveDate;
 use stablecoin_backend::config::ChainConfig;
+use stablecoin_backend::daemon::{JobKind, ScheduleFile};
+use stablecoin_backend::error::{self, KeeperError};
+use stablecoin_backend::fees::{gwei_to_wei, FeeStrategy};
+use stablecoin_backend::gas_ceiling::{decide, Deferral, GasDecision};
+use stablecoin_backend::jobs::boost_rewards::{BoostRewardsJob, CampaignConfig, CampaignStatus};
+use stablecoin_backend::jobs::boost_rewards_s3::{BoostRewardsS3, CampaignSummary};
+use stablecoin_backend::jobs::JobOutcome;
+use stablecoin_backend::metrics::metrics;
+use stablecoin_backend::nonce_manager::NonceManager;
 use stablecoin_backend::retry::{execute_with_retry, RetryConfig};
+use stablecoin_backend::run_lock::{
+    AcquireOutcome, FileLockBackend, LockBackend, LockRecord, RunLock,
+};
 use stablecoin_backend::transaction_monitor::{TransactionReceipt, TransactionStatus};
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
@@ -116,12 +131,20 @@ async fn test_transaction_receipt_creation() -> Result<()> {
         block_number: 12345,
         gas_used: U256::from(21000),
         status: TransactionStatus::Success,
+        effective_gas_price: 2_000_000_000,
+        l1_fee: Some(U256::from(5_000u64)),
+        estimated_cost_wei: None,
     };
 
     assert_eq!(receipt.hash, hash);
     assert_eq!(receipt.block_number, 12345);
     assert_eq!(receipt.gas_used, U256::from(21000));
     assert_eq!(receipt.status, TransactionStatus::Success);
+    // L2 execution plus the OP-stack L1 data fee
+    assert_eq!(
+        receipt.total_cost_wei(),
+        U256::from(21000u64 * 2_000_000_000 + 5_000)
+    );
 
     println!("✅ Transaction receipt creation test passed");
     Ok(())
@@ -334,3 +357,629 @@ region = "us-east-1"
     println!("✅ Chain-specific configuration test passed");
     Ok(())
 }
+
+#[tokio::test]
+async fn test_boost_token_limits() -> Result<()> {
+    // Test that boost campaigns are checked against the token allowlist and caps
+    let config_content = r#"
+[chain]
+chain_id = 1946
+rpc_url = "https://rpc.minato.soneium.org"
+
+[contracts]
+usdsc_address = "0x1234567890123456789012345678901234567890"
+earn_vault_address = "0x0987654321098765432109876543210987654321"
+
+[retry]
+max_attempts = 3
+base_delay_seconds = 5
+max_delay_seconds = 300
+backoff_multiplier = 2.0
+
+[monitoring]
+transaction_timeout_seconds = 300
+poll_interval_seconds = 5
+timeout_block_number = 0
+timeout_gas_used = "0"
+
+[transaction]
+value_wei = "0"
+
+[kms]
+key_id = "test-kms-key-id"
+region = "us-east-1"
+
+[[boost_tokens]]
+address = "0x7e426d026f604d1c47b50059752122d8ab1e2c28"
+max_daily_amount = 100.0
+max_campaign_amount = 1000.0
+"#;
+
+    let temp_file = std::env::temp_dir().join(format!(
+        "test_config_{}_{}.toml",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+    std::fs::write(&temp_file, config_content)?;
+    let config = ChainConfig::load(temp_file.to_str().unwrap())?;
+    std::fs::remove_file(&temp_file)?;
+
+    let allowed_token = "0x7E426D026F604D1C47B50059752122D8AB1E2C28";
+    let other_token = "0x1234567890123456789012345678901234567890";
+
+    // Within limits: 900 over 10 days = 90/day
+    let job = BoostRewardsJob::new(
+        config.clone(),
+        allowed_token.to_string(),
+        900.0,
+        "2025-01-01".to_string(),
+        "2025-01-10".to_string(),
+        None,
+        true,
+    );
+    assert!(job.is_ok());
+
+    // Token not in allowlist
+    let job = BoostRewardsJob::new(
+        config.clone(),
+        other_token.to_string(),
+        10.0,
+        "2025-01-01".to_string(),
+        "2025-01-10".to_string(),
+        None,
+        true,
+    );
+    assert!(job.is_err());
+
+    // Over max_campaign_amount
+    let job = BoostRewardsJob::new(
+        config.clone(),
+        allowed_token.to_string(),
+        2000.0,
+        "2025-01-01".to_string(),
+        "2025-01-31".to_string(),
+        None,
+        true,
+    );
+    assert!(job.is_err());
+
+    // Over max_daily_amount: 500 over 2 days = 250/day
+    let campaign = CampaignConfig {
+        id: "too-fast".to_string(),
+        token_address: Address::from_str(allowed_token)?,
+        total_amount: 500.0,
+        start_date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+        end_date: NaiveDate::from_ymd_opt(2025, 1, 2).unwrap(),
+        status: CampaignStatus::Active,
+    };
+    let job = BoostRewardsJob::from_campaign_config(config.clone(), campaign, true);
+    assert!(job.is_err());
+
+    // No allowlist refuses every token unless allow_any_token opts out
+    let mut config = config;
+    config.boost_tokens = None;
+    let new_job = |config: ChainConfig| {
+        BoostRewardsJob::new(
+            config,
+            other_token.to_string(),
+            10.0,
+            "2025-01-01".to_string(),
+            "2025-01-10".to_string(),
+            None,
+            true,
+        )
+    };
+    assert!(new_job(config.clone()).is_err());
+    config.boost.allow_any_token = true;
+    assert!(new_job(config).is_ok());
+
+    println!("✅ Boost token limits test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_nonce_manager_allocation() -> Result<()> {
+    // Test that nonces are handed out consecutively and released nonces are reused
+    let asserter = Asserter::new();
+    let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+    let nonce_manager = NonceManager::new(Arc::new(provider), Address::ZERO);
+
+    // First allocation reads the pending transaction count
+    asserter.push_success(&U64::from(5));
+    assert_eq!(nonce_manager.next_nonce().await?, 5);
+    assert_eq!(nonce_manager.next_nonce().await?, 6);
+
+    // Releasing the latest nonce hands it out again
+    assert!(nonce_manager.release(6).await);
+    assert_eq!(nonce_manager.next_nonce().await?, 6);
+
+    // A released older nonce is reused first, without asking the node while 6 is
+    // still out: its pending count would hand out 6 a second time
+    assert!(!nonce_manager.release(5).await);
+    assert_eq!(nonce_manager.next_nonce().await?, 5);
+    assert_eq!(nonce_manager.next_nonce().await?, 7);
+
+    // Batches come in increasing order, filling released gaps lowest first
+    nonce_manager.release(5).await;
+    assert_eq!(nonce_manager.next_nonces(2).await?, vec![5, 8]);
+
+    // Releasing the latest ones shrinks back past released nonces below them
+    assert!(!nonce_manager.release(7).await);
+    assert!(nonce_manager.release(8).await);
+    assert_eq!(nonce_manager.next_nonces(2).await?, vec![7, 8]);
+
+    // A gap claimed back for filling is no longer handed out
+    assert!(!nonce_manager.release(7).await);
+    assert!(nonce_manager.claim_released(7).await);
+    assert!(!nonce_manager.claim_released(7).await);
+    assert_eq!(nonce_manager.next_nonce().await?, 9);
+
+    println!("✅ Nonce manager allocation test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_daemon_schedule_loading() -> Result<()> {
+    // Test that schedule files are parsed and validated
+    let schedule_content = r#"
+[[jobs]]
+name = "claim"
+job = "claim-yield"
+schedule = "*/10 * * * *"
+config = "configs/ethereum-mainnet.toml"
+
+[[jobs]]
+name = "boost"
+job = "boost-rewards-s3"
+schedule = "0 0 12 * * *"
+config = "configs/soneium-mainnet.toml"
+campaigns_s3 = "s3://bucket/campaigns.toml"
+"#;
+
+    let temp_file = std::env::temp_dir().join(format!(
+        "test_schedule_{}_{}.toml",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+    std::fs::write(&temp_file, schedule_content)?;
+    let schedule = ScheduleFile::load(temp_file.to_str().unwrap())?;
+
+    assert_eq!(schedule.jobs.len(), 2);
+    assert_eq!(schedule.jobs[0].job, JobKind::ClaimYield);
+    assert_eq!(schedule.jobs[1].job, JobKind::BoostRewardsS3);
+    assert!(!schedule.jobs[0].dry_run);
+
+    // 5-field expressions fire every 10 minutes on the minute
+    let upcoming: Vec<_> = schedule.jobs[0]
+        .cron_schedule()?
+        .upcoming(chrono::Utc)
+        .take(2)
+        .collect();
+    assert_eq!((upcoming[1] - upcoming[0]).num_minutes(), 10);
+
+    // Boost jobs without campaigns_s3 are rejected
+    std::fs::write(
+        &temp_file,
+        r#"
+[[jobs]]
+name = "boost"
+job = "boost-rewards-s3"
+schedule = "0 12 * * *"
+config = "configs/soneium-mainnet.toml"
+"#,
+    )?;
+    assert!(ScheduleFile::load(temp_file.to_str().unwrap()).is_err());
+
+    // Invalid cron expressions are rejected
+    std::fs::write(
+        &temp_file,
+        r#"
+[[jobs]]
+name = "claim"
+job = "claim-yield"
+schedule = "every ten minutes"
+config = "configs/ethereum-mainnet.toml"
+"#,
+    )?;
+    assert!(ScheduleFile::load(temp_file.to_str().unwrap()).is_err());
+    std::fs::remove_file(&temp_file)?;
+
+    println!("✅ Daemon schedule loading test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_file_run_lock() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("keeper_lock_test_{}", std::process::id()));
+    let backend = FileLockBackend::new(&dir);
+    let record = |holder: &str, acquired_at: u64, expires_at: u64| LockRecord {
+        holder: holder.to_string(),
+        job: "claim-yield".to_string(),
+        acquired_at,
+        expires_at,
+    };
+
+    // First holder gets the lock, a second one sees who holds it
+    let first = record("host-a:1", 1_000, 2_000);
+    assert_eq!(
+        backend.try_acquire("keeper-1", &first).await?,
+        AcquireOutcome::Acquired
+    );
+    assert_eq!(
+        backend
+            .try_acquire("keeper-1", &record("host-b:2", 1_500, 2_500))
+            .await?,
+        AcquireOutcome::HeldBy(first.clone())
+    );
+
+    // Only the holder can renew; an expired lease can be taken over
+    assert!(
+        backend
+            .renew("keeper-1", &record("host-a:1", 1_000, 3_000))
+            .await?
+    );
+    assert!(
+        !backend
+            .renew("keeper-1", &record("host-b:2", 1_000, 3_000))
+            .await?
+    );
+    assert_eq!(
+        backend
+            .try_acquire("keeper-1", &record("host-b:2", 3_000, 4_000))
+            .await?,
+        AcquireOutcome::Acquired
+    );
+
+    // Release by a non-holder is ignored, release by the holder frees the lock
+    backend.release("keeper-1", "host-a:1").await?;
+    assert!(matches!(
+        backend
+            .try_acquire("keeper-1", &record("host-c:3", 3_500, 4_500))
+            .await?,
+        AcquireOutcome::HeldBy(_)
+    ));
+    backend.release("keeper-1", "host-b:2").await?;
+    assert_eq!(
+        backend
+            .try_acquire("keeper-1", &record("host-c:3", 3_500, 4_500))
+            .await?,
+        AcquireOutcome::Acquired
+    );
+
+    // Every acquisition has its own holder, so a second job in the same process is refused
+    let lock = RunLock::new(
+        Arc::new(FileLockBackend::new(&dir)),
+        Duration::from_secs(60),
+    );
+    let guard = lock.acquire("keeper-2", "distribute-rewards").await?;
+    let other = RunLock::new(
+        Arc::new(FileLockBackend::new(&dir)),
+        Duration::from_secs(60),
+    );
+    match other.acquire("keeper-2", "distribute-rewards").await {
+        Err(e) => assert!(matches!(
+            e.downcast_ref::<KeeperError>(),
+            Some(KeeperError::LockHeld(_))
+        )),
+        Ok(_) => panic!("second acquisition should be refused"),
+    }
+    guard.release().await;
+    other
+        .acquire("keeper-2", "distribute-rewards")
+        .await?
+        .release()
+        .await;
+
+    // Losing the lease mid-run stops the guarded work instead of letting it keep sending
+    let lock = RunLock::new(Arc::new(FileLockBackend::new(&dir)), Duration::from_secs(3));
+    let guard = lock.acquire("keeper-3", "claim-yield").await?;
+    std::fs::write(
+        dir.join("keeper-3.json"),
+        serde_json::to_vec(&record("host-d:4", 1_000, u64::MAX))?,
+    )?;
+    let err = guard
+        .guard(std::future::pending::<Result<()>>())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<KeeperError>(),
+        Some(KeeperError::LockHeld(_))
+    ));
+    guard.release().await;
+
+    std::fs::remove_dir_all(&dir)?;
+    println!("✅ File run lock test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_metrics_recording() -> Result<()> {
+    let config = RetryConfig::new(3, Duration::from_millis(1), Duration::from_millis(5), 2.0);
+    let attempts = Arc::new(Mutex::new(0));
+
+    // One failed and one successful attempt are counted separately per operation
+    let counter = attempts.clone();
+    execute_with_retry(
+        || {
+            let counter = counter.clone();
+            async move {
+                let mut count = counter.lock().unwrap();
+                *count += 1;
+                if *count == 1 {
+                    Err("transient")
+                } else {
+                    Ok(())
+                }
+            }
+        },
+        &config,
+        "Metrics test operation",
+    )
+    .await?;
+    let retry_attempts = &metrics().retry_attempts;
+    assert_eq!(
+        retry_attempts
+            .with_label_values(&["Metrics test operation", "failure"])
+            .get(),
+        1
+    );
+    assert_eq!(
+        retry_attempts
+            .with_label_values(&["Metrics test operation", "success"])
+            .get(),
+        1
+    );
+
+    metrics().record_job_run("metrics-test-job", true, Duration::from_secs(2));
+    metrics().record_pending_yield(424242, U256::from(1_500_000u64));
+
+    let rendered = metrics().render();
+    assert!(
+        rendered.contains(r#"keeper_job_runs_total{job="metrics-test-job",outcome="success"} 1"#)
+    );
+    assert!(rendered.contains(r#"keeper_pending_yield_wei{chain_id="424242"} 1500000"#));
+    assert!(rendered.contains("keeper_retry_attempts_total"));
+
+    println!("✅ Metrics recording test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_exit_codes() -> Result<()> {
+    assert_eq!(JobOutcome::Completed.exit_code(), error::EXIT_SUCCESS);
+    assert_eq!(JobOutcome::NoOp.exit_code(), error::EXIT_NO_OP);
+
+    // The class survives context and is found anywhere in the chain
+    let err = anyhow::Error::new(KeeperError::Kms("access denied".to_string()))
+        .context("Signer initialization failed");
+    assert_eq!(error::exit_code(&err), error::EXIT_KMS);
+    assert_eq!(
+        error::exit_code(&anyhow::anyhow!("something else")),
+        error::EXIT_UNCLASSIFIED
+    );
+
+    // Permanent failures stop retrying at once and keep their class
+    let config = RetryConfig {
+        max_attempts: 3,
+        base_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(1),
+        backoff_multiplier: 1.0,
+    };
+    let attempts = Mutex::new(0);
+    let result: Result<()> = execute_with_retry(
+        || {
+            *attempts.lock().unwrap() += 1;
+            async {
+                Err(anyhow::Error::new(KeeperError::InsufficientFunds(
+                    "insufficient funds for gas".to_string(),
+                )))
+            }
+        },
+        &config,
+        "Exit code test operation",
+    )
+    .await;
+    assert_eq!(*attempts.lock().unwrap(), 1);
+    assert_eq!(
+        error::exit_code(&result.unwrap_err()),
+        error::EXIT_INSUFFICIENT_FUNDS
+    );
+
+    // Boost runs: nothing active is a no-op, any failed campaign fails the run
+    assert_eq!(BoostRewardsS3::outcome(&[])?, JobOutcome::NoOp);
+    let failed = CampaignSummary {
+        campaign_id: "campaign-1".to_string(),
+        token_address: Address::ZERO,
+        result: Err("reverted".to_string()),
+    };
+    let err = BoostRewardsS3::outcome(&[failed]).unwrap_err();
+    assert_eq!(error::exit_code(&err), error::EXIT_CAMPAIGNS_FAILED);
+
+    println!("✅ Exit code test passed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fee_strategy() -> Result<()> {
+    use alloy::rpc::types::FeeHistory;
+    use stablecoin_backend::config::{GasPricing, TransactionSettings};
+
+    // Fixed tip: base fee * multiplier + tip, cut down to the cap
+    let settings: TransactionSettings = toml::from_str(
+        r#"
+value_wei = "0"
+max_priority_fee_gwei = 1.0
+base_fee_multiplier = 2.0
+max_fee_per_gas_gwei = 50.0
+"#,
+    )?;
+    let strategy = FeeStrategy::from_settings(&settings);
+    let quote = strategy.quote(gwei_to_wei(10.0), gwei_to_wei(1.0));
+    assert_eq!(quote.max_fee_per_gas, gwei_to_wei(21.0));
+    assert!(!quote.capped);
+    let quote = strategy.quote(gwei_to_wei(30.0), gwei_to_wei(1.0));
+    assert_eq!(quote.max_fee_per_gas, gwei_to_wei(50.0));
+    assert!(quote.capped);
+
+    // Fee history tip: median of the per-block percentile rewards, next block's base fee
+    let settings: TransactionSettings = toml::from_str(
+        r#"
+value_wei = "0"
+tip_strategy = "fee_history"
+fee_history_percentile = 60.0
+fee_history_blocks = 3
+"#,
+    )?;
+    let asserter = Asserter::new();
+    let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+    asserter.push_success(&FeeHistory {
+        oldest_block: 100,
+        base_fee_per_gas: vec![8, 9, 10, 12],
+        gas_used_ratio: vec![0.5, 0.6, 0.7],
+        reward: Some(vec![vec![5], vec![1], vec![3]]),
+        ..Default::default()
+    });
+    let quote = FeeStrategy::from_settings(&settings)
+        .estimate(&provider)
+        .await?;
+    assert_eq!(quote.base_fee_per_gas, 12);
+    assert_eq!(quote.max_priority_fee_per_gas, 3);
+    assert_eq!(quote.max_fee_per_gas, 12 * 2 + 3);
+    assert!(!quote.legacy);
+
+    // Legacy: eth_gasPrice * multiplier under the same cap, applied as gasPrice
+    let settings: TransactionSettings = toml::from_str(
+        r#"
+value_wei = "0"
+gas_price_multiplier = 1.5
+max_fee_per_gas_gwei = 50.0
+"#,
+    )?;
+    let strategy = FeeStrategy::from_settings(&settings);
+    let quote = strategy.legacy_quote(gwei_to_wei(20.0));
+    assert!(quote.legacy && !quote.capped);
+    assert_eq!(quote.max_fee_per_gas, gwei_to_wei(30.0));
+    assert_eq!(quote.expected_gas_price(), gwei_to_wei(30.0));
+    assert!(strategy.legacy_quote(gwei_to_wei(40.0)).capped);
+    let mut tx = alloy::rpc::types::TransactionRequest::default();
+    quote.apply(&mut tx);
+    assert_eq!(tx.gas_price, Some(gwei_to_wei(30.0)));
+    assert_eq!(tx.max_fee_per_gas, None);
+
+    // Auto: a chain reporting no base fee falls back to eth_gasPrice
+    asserter.push_success(&FeeHistory {
+        oldest_block: 100,
+        base_fee_per_gas: vec![0, 0],
+        gas_used_ratio: vec![0.5],
+        reward: Some(vec![vec![0]]),
+        ..Default::default()
+    });
+    asserter.push_success(&"0x3b9aca00");
+    let quote = strategy.estimate(&provider).await?;
+    assert!(quote.legacy);
+    assert_eq!(quote.max_fee_per_gas, 1_500_000_000);
+
+    // Forced EIP-1559 refuses such a chain instead
+    asserter.push_success(&FeeHistory {
+        oldest_block: 100,
+        base_fee_per_gas: vec![0, 0],
+        gas_used_ratio: vec![0.5],
+        reward: Some(vec![vec![0]]),
+        ..Default::default()
+    });
+    assert!(strategy
+        .with_pricing(GasPricing::Eip1559)
+        .estimate(&provider)
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_gas_ceiling_deferral() -> Result<()> {
+    use chrono::{Duration as ChronoDuration, Utc};
+    use stablecoin_backend::config::GasCeilingSettings;
+
+    let ceiling = GasCeilingSettings {
+        max_base_fee_gwei: 30.0,
+        max_deferral_seconds: Some(3600),
+    };
+    let now = Utc::now();
+    let minutes_ago = |minutes: i64| now - ChronoDuration::minutes(minutes);
+
+    // Under the ceiling always proceeds
+    assert_eq!(
+        decide(&ceiling, gwei_to_wei(25.0), None, now),
+        GasDecision::Proceed
+    );
+
+    // Above it defers, counting from the first deferral of the streak
+    assert_eq!(
+        decide(&ceiling, gwei_to_wei(80.0), None, now),
+        GasDecision::Defer {
+            deferred_for: Duration::ZERO
+        }
+    );
+    let streak = Deferral {
+        since: minutes_ago(30),
+        last: minutes_ago(10),
+    };
+    assert_eq!(
+        decide(&ceiling, gwei_to_wei(80.0), Some(streak), now),
+        GasDecision::Defer {
+            deferred_for: Duration::from_secs(1800)
+        }
+    );
+
+    // Past max_deferral_seconds the urgency override acts anyway
+    let streak = Deferral {
+        since: minutes_ago(70),
+        last: minutes_ago(10),
+    };
+    assert!(matches!(
+        decide(&ceiling, gwei_to_wei(80.0), Some(streak), now),
+        GasDecision::Urgent { .. }
+    ));
+
+    // A stale streak (last deferral long ago) starts over instead of triggering urgency
+    let stale = Deferral {
+        since: minutes_ago(3 * 24 * 60),
+        last: minutes_ago(2 * 24 * 60),
+    };
+    assert_eq!(
+        decide(&ceiling, gwei_to_wei(80.0), Some(stale), now),
+        GasDecision::Defer {
+            deferred_for: Duration::ZERO
+        }
+    );
+    assert_eq!(JobOutcome::Deferred.exit_code(), error::EXIT_DEFERRED);
+
+    Ok(())
+}
+
+#[test]
+fn test_profitability_threshold() -> Result<()> {
+    use stablecoin_backend::profitability::{cost_in_usdsc, required_yield};
+
+    // 200k gas at 10 gwei = 0.002 native; at 2500 USDSC per native that's 5 USDSC
+    let cost_wei = U256::from(200_000u64) * U256::from(gwei_to_wei(10.0));
+    assert_eq!(cost_in_usdsc(cost_wei, 2500.0, 6), U256::from(5_000_000u64));
+    assert_eq!(
+        cost_in_usdsc(cost_wei, 2500.0, 18),
+        U256::from(5_000_000_000_000_000_000u128)
+    );
+
+    // Yield has to cover cost x multiple
+    let cost_usdsc = U256::from(5_000_000u64);
+    assert_eq!(required_yield(cost_usdsc, 3.0), U256::from(15_000_000u64));
+    assert_eq!(required_yield(U256::ZERO, 3.0), U256::ZERO);
+
+    Ok(())
+}